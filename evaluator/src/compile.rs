@@ -1,7 +1,8 @@
 use shared::build_native_shell_command;
 use std::collections::HashMap;
+use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::config::TEMP_DIR;
@@ -11,18 +12,127 @@ use crate::utils::TEMP_FILE_EXE;
 
 type Placeholders<'a> = HashMap<&'a str, &'a str>;
 
+/// 編譯階段的行為設定。
+#[derive(Debug, Default)]
+pub struct CompileOptions {
+    /// 為 `true` 時緩衝編譯器輸出，僅在編譯失敗時顯示；否則即時輸出。
+    pub capture_output: bool,
+    /// 接在語言設定 `compile_args` 之後的額外編譯參數。
+    pub extra_args: Vec<String>,
+    /// `--lang-bin` 指定的 (鍵, 程式路徑)，鍵為語言副檔名或指令開頭的程式名稱，後者優先。
+    pub program_overrides: Vec<(String, String)>,
+}
+
+impl CompileOptions {
+    /// 找出 `template` 開頭程式的替換路徑。
+    ///
+    /// 以語言副檔名指定時只替換該語言第一個階段 (`primary`) 的程式，
+    /// 例如 `cpp=g++-13` 替換編譯器而非編譯產物。
+    fn program_override(
+        &self,
+        template: &str,
+        lang_profile: &LanguageProfile,
+        primary: bool,
+    ) -> Option<&str> {
+        let program = template.split_whitespace().next()?;
+        // 程式名稱優先於語言副檔名；同一個鍵重複指定時以最後一個為準
+        let mut overrides = self.program_overrides.iter().rev();
+        let by_program = overrides.clone().find(|(key, _)| key == program);
+        let by_language = || overrides.find(|(key, _)| primary && lang_profile.matches(key));
+        by_program
+            .or_else(by_language)
+            .map(|(_, path)| path.as_str())
+    }
+}
+
 fn build_command_from_template(
     template: &str,
     placeholders: &Placeholders,
+    program: Option<&str>,
 ) -> Result<Command, io::Error> {
-    let mut final_command_str = template.to_string();
+    let mut final_command_str = match program {
+        Some(program) => replace_program(template, program),
+        None => template.to_string(),
+    };
     for (key, value) in placeholders {
         final_command_str = final_command_str.replace(&format!("{{{key}}}"), value);
     }
 
+    log::debug!("command: {final_command_str}");
     build_native_shell_command(&final_command_str)
 }
 
+/// 將指令開頭的程式換成 `program`，路徑含空白時加上引號。
+fn replace_program(command: &str, program: &str) -> String {
+    let args = command
+        .trim_start()
+        .split_once(char::is_whitespace)
+        .map_or("", |(_, args)| args);
+    let program = if program.contains(char::is_whitespace) {
+        // PowerShell 需以 `&` 執行引號中的路徑
+        let call = if cfg!(windows) { "& " } else { "" };
+        format!("{call}\"{program}\"")
+    } else {
+        program.to_owned()
+    };
+    format!("{program} {args}").trim_end().to_owned()
+}
+
+/// 編譯指令以 `-o`/`--output` 指定的輸出路徑，未指定時回傳 `None`。
+fn output_argument(command: &Command) -> Option<PathBuf> {
+    // Windows 上整段指令為單一參數，因此再以空白切分
+    let args: Vec<String> = command
+        .get_args()
+        .flat_map(|arg| {
+            arg.to_string_lossy()
+                .split_whitespace()
+                .map(|token| token.trim_matches(['"', '\'']).to_owned())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    args.iter().enumerate().find_map(|(index, arg)| {
+        let path = match arg.as_str() {
+            "-o" | "--output" => args.get(index + 1)?.as_str(),
+            _ => arg
+                .strip_prefix("--output=")
+                .or_else(|| arg.strip_prefix("-o").filter(|path| !path.is_empty()))?,
+        };
+        Some(PathBuf::from(path))
+    })
+}
+
+/// 確認輸出路徑不是原始碼本身，避免設定錯誤的模板覆寫使用者的原始碼。
+///
+/// 兩者皆解析 `..` 與符號連結後再比較。
+fn ensure_not_source<'a>(output: &Path, source: &Path) -> Result<(), CompileError<'a>> {
+    let same_file = match (resolve_output_path(output), fs::canonicalize(source)) {
+        (Some(output), Ok(source)) => output == source,
+        _ => false,
+    };
+    if same_file {
+        return Err(CompileError::SE(
+            format!(
+                "Refusing to compile: output path '{}' is the source file itself. Check the compile template in config.",
+                output.display()
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// 輸出檔可能尚未存在，此時解析其所在的資料夾再接上檔名。
+fn resolve_output_path(output: &Path) -> Option<PathBuf> {
+    if let Ok(path) = fs::canonicalize(output) {
+        return Some(path);
+    }
+    let parent = output
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    Some(fs::canonicalize(parent).ok()?.join(output.file_name()?))
+}
+
 /// 根據原始碼檔案準備一個最終可執行的指令。
 ///
 /// 對於編譯型語言，此函式會執行編譯，並在成功後回傳一個執行已編譯產物的指令。
@@ -30,8 +140,8 @@ fn build_command_from_template(
 ///
 /// # Arguments
 /// * `file_path` - 原始碼檔案的路徑。
-/// * `file_type` - 程式語言的枚舉。
-/// * `config` - 已載入的評測器設定。
+/// * `lang_profile` - 對應副檔名的語言設定。
+/// * `options` - 編譯階段的行為設定。
 ///
 /// # Returns
 /// * `Ok(Command)` - 一個準備好執行的 `Command`。
@@ -39,6 +149,7 @@ fn build_command_from_template(
 pub fn prepare_command<'a>(
     file_path: &'a str,
     lang_profile: &'a LanguageProfile,
+    options: &CompileOptions,
 ) -> Result<Command, CompileError<'a>> {
     let source_path_normalized = file_path.replace('\\', "/");
 
@@ -74,20 +185,37 @@ pub fn prepare_command<'a>(
         placeholders.insert("output_folder", &output_folder_normalized);
         placeholders.insert("source_stem", source_filename_stem);
 
+        let compile_args = lang_profile
+            .compile_args
+            .iter()
+            .flatten()
+            .chain(&options.extra_args)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let compile_template =
+            if compile_args.is_empty() || compile_instruction.command.contains("{compile_args}") {
+                compile_instruction.command.clone()
+            } else {
+                format!("{} {{compile_args}}", compile_instruction.command)
+            };
+        placeholders.insert("compile_args", &compile_args);
+
+        let program = options.program_override(&compile_template, lang_profile, true);
         let mut compile_cmd =
-            build_command_from_template(&compile_instruction.command, &placeholders)
+            build_command_from_template(&compile_template, &placeholders, program)
                 .map_err(|e| CompileError::SE(e.to_string().into()))?;
 
-        let compile_status = compile_cmd.status().map_err(|e| {
-            CompileError::SE(format!("Failed to execute compile command: {e}").into())
-        })?;
-
-        if !compile_status.success() {
-            return Err(CompileError::CE("Failed to compile source code.".into()));
+        ensure_not_source(&output_path, source_path)?;
+        if let Some(output_argument) = output_argument(&compile_cmd) {
+            ensure_not_source(&output_argument, source_path)?;
         }
 
+        run_compile_command(&mut compile_cmd, options)?;
+
         if let Some(run_instruction) = &lang_profile.run {
-            build_command_from_template(&run_instruction.command, &placeholders)
+            let program = options.program_override(&run_instruction.command, lang_profile, false);
+            build_command_from_template(&run_instruction.command, &placeholders, program)
                 .map_err(|e| CompileError::SE(e.to_string().into()))
         } else {
             Ok(Command::new(&output_path_normalized))
@@ -96,7 +224,8 @@ pub fn prepare_command<'a>(
         let mut placeholders = Placeholders::new();
         placeholders.insert("source", &source_path_normalized);
 
-        build_command_from_template(&run_instruction.command, &placeholders)
+        let program = options.program_override(&run_instruction.command, lang_profile, true);
+        build_command_from_template(&run_instruction.command, &placeholders, program)
             .map_err(|e| CompileError::SE(e.to_string().into()))
     } else {
         Err(CompileError::SE(
@@ -108,3 +237,120 @@ pub fn prepare_command<'a>(
         ))
     }
 }
+
+fn run_compile_command<'a>(
+    compile_cmd: &mut Command,
+    options: &CompileOptions,
+) -> Result<(), CompileError<'a>> {
+    let map_spawn_error =
+        |e: io::Error| CompileError::SE(format!("Failed to execute compile command: {e}").into());
+
+    if options.capture_output {
+        let output = compile_cmd.output().map_err(map_spawn_error)?;
+        if !output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(CompileError::CE(
+                format!(
+                    "Failed to compile source code.\n{}",
+                    [stdout.trim_end(), stderr.trim_end()]
+                        .into_iter()
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                )
+                .into(),
+            ));
+        }
+    } else {
+        let status = compile_cmd.status().map_err(map_spawn_error)?;
+        if !status.success() {
+            return Err(CompileError::CE("Failed to compile source code.".into()));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_argument() {
+        let command = |args: &[&str]| {
+            let mut command = Command::new("g++");
+            command.args(args);
+            command
+        };
+        assert_eq!(
+            output_argument(&command(&["a.cpp", "-o", "a.cpp"])),
+            Some(PathBuf::from("a.cpp"))
+        );
+        assert_eq!(
+            output_argument(&command(&["a.cpp", "-oout.exe"])),
+            Some(PathBuf::from("out.exe"))
+        );
+        assert_eq!(
+            output_argument(&command(&["--output=b.exe", "a.cpp"])),
+            Some(PathBuf::from("b.exe"))
+        );
+        assert_eq!(output_argument(&command(&["-O2", "a.cpp"])), None);
+    }
+
+    #[test]
+    fn test_program_override() {
+        let profile: LanguageProfile =
+            serde_yml::from_str("extension: cpp\naliases: [cc]\n").unwrap();
+        let options = CompileOptions {
+            program_overrides: vec![
+                ("cc".to_owned(), "/usr/bin/g++-13".to_owned()),
+                ("python".to_owned(), "/opt/py311/bin/python".to_owned()),
+            ],
+            ..Default::default()
+        };
+        let compile = "g++ -O2 {source} -o {output}";
+        assert_eq!(
+            options.program_override(compile, &profile, true),
+            Some("/usr/bin/g++-13")
+        );
+        assert_eq!(options.program_override("{output}", &profile, false), None);
+        assert_eq!(
+            options.program_override("python {source}", &profile, false),
+            Some("/opt/py311/bin/python")
+        );
+
+        let ordered = CompileOptions {
+            program_overrides: vec![
+                ("g++".to_owned(), "/a".to_owned()),
+                ("cpp".to_owned(), "/b".to_owned()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            ordered.program_override(compile, &profile, true),
+            Some("/a")
+        );
+
+        assert_eq!(
+            replace_program(compile, "/usr/bin/g++-13"),
+            "/usr/bin/g++-13 -O2 {source} -o {output}"
+        );
+    }
+
+    #[test]
+    fn test_ensure_not_source() {
+        let dir =
+            std::env::temp_dir().join(format!("offline-judge-not-source-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let source = dir.join("a.cpp");
+        fs::write(&source, "").unwrap();
+
+        assert!(ensure_not_source(&dir.join(".").join("a.cpp"), &source).is_err());
+        assert!(ensure_not_source(&dir.join("src").join("..").join("a.cpp"), &source).is_err());
+        assert!(ensure_not_source(&dir.join("src").join("a.exe"), &source).is_ok());
+        assert!(ensure_not_source(&dir.join("a.exe"), &source).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
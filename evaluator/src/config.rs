@@ -2,8 +2,12 @@ use num_format::Locale;
 use std::env;
 use std::path::PathBuf;
 use std::sync::LazyLock;
+use std::sync::atomic::AtomicBool;
 
 pub static TEMP_DIR: LazyLock<PathBuf> =
     LazyLock::new(|| env::temp_dir().join(env!("CARGO_PKG_NAME")));
 
 pub const NUMBER_FORMAT: Locale = Locale::en;
+
+/// 顯示數字時是否加上千分位分隔符，可由設定檔或 `--no-group` 關閉。
+pub static NUMBER_GROUPING: AtomicBool = AtomicBool::new(true);
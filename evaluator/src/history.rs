@@ -0,0 +1,90 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::TEMP_DIR;
+use crate::judge::verdict::VerdictKind;
+use crate::reader::TestCase;
+
+const HISTORY_FILE_NAME: &str = "verdicts.yaml";
+
+/// 各程式檔上次評測的結果，以測資編號 (從 1 起算) 為鍵。
+pub type CaseVerdicts = BTreeMap<usize, CaseVerdict>;
+
+/// 單筆測資上次的結果，連同當時測資內容的雜湊值。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaseVerdict {
+    pub kind: VerdictKind,
+    /// 見 [`case_digest`]；測資被修改或重新排序後與目前的測資不符，該筆紀錄即不再採用。
+    pub digest: u64,
+}
+
+impl CaseVerdict {
+    pub fn new(kind: VerdictKind, case: &TestCase) -> Self {
+        Self {
+            kind,
+            digest: case_digest(case),
+        }
+    }
+
+    /// 紀錄屬於 `case` 且結果為 AC。
+    pub fn accepted(&self, case: &TestCase) -> bool {
+        self.kind == VerdictKind::AC && self.digest == case_digest(case)
+    }
+}
+
+/// 測資輸入、答案與參數的雜湊值。
+fn case_digest(case: &TestCase) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    case.input_bytes().hash(&mut hasher);
+    case.answer_bytes().hash(&mut hasher);
+    case.args.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct VerdictHistory {
+    files: HashMap<String, CaseVerdicts>,
+}
+
+fn history_path() -> PathBuf {
+    TEMP_DIR.join(HISTORY_FILE_NAME)
+}
+
+fn history_key(file: &str) -> String {
+    std::path::absolute(Path::new(file))
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| file.to_owned())
+}
+
+fn load_history() -> VerdictHistory {
+    fs::read_to_string(history_path())
+        .ok()
+        .and_then(|contents| serde_yml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// 讀取 `file` 上次評測的各測資結果，沒有紀錄時回傳 `None`。
+pub fn load_verdicts(file: &str) -> Option<CaseVerdicts> {
+    load_history().files.remove(&history_key(file))
+}
+
+/// 記錄 `file` 本次評測的結果。
+///
+/// `merge` 為真時只覆寫本次評測到的測資，其餘沿用上次的結果。
+pub fn save_verdicts(file: &str, verdicts: CaseVerdicts, merge: bool) -> io::Result<()> {
+    let mut history = load_history();
+    let entry = history.files.entry(history_key(file)).or_default();
+    if merge {
+        entry.extend(verdicts);
+    } else {
+        *entry = verdicts;
+    }
+
+    let yaml = serde_yml::to_string(&history).map_err(io::Error::other)?;
+    fs::write(history_path(), yaml)
+}
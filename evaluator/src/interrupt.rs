@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+/// 目前正在評測的子程序 PID，0 表示沒有。
+static CURRENT_CHILD: AtomicU32 = AtomicU32::new(0);
+
+/// 註冊 Ctrl+C 處理程序：記錄中斷並結束目前的子程序，讓評測迴圈能印出已完成測資的總結。
+pub fn install_handler() {
+    let result = ctrlc::set_handler(|| {
+        INTERRUPTED.store(true, Ordering::SeqCst);
+        kill_child(CURRENT_CHILD.load(Ordering::SeqCst));
+    });
+    if let Err(e) = result {
+        log::warn!("無法設定 Ctrl+C 處理程序: {e}");
+    }
+}
+
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// 記錄目前的子程序，傳入 `None` 表示子程序已結束。
+pub fn track_child(pid: Option<u32>) {
+    CURRENT_CHILD.store(pid.unwrap_or(0), Ordering::SeqCst);
+}
+
+/// 結束子程序所在的整個程序群組，避免其子程序繼續佔用輸出管線；
+/// 子程序不是群組領導者 (例如無評判模式) 時只結束它本身。
+#[cfg(unix)]
+fn kill_child(pid: u32) {
+    if pid != 0 {
+        let pid = pid as libc::pid_t;
+        // SAFETY: kill 僅送出訊號，不涉及記憶體操作。
+        unsafe {
+            if libc::kill(-pid, libc::SIGKILL) != 0 {
+                libc::kill(pid, libc::SIGKILL);
+            }
+        }
+    }
+}
+
+/// Windows 的 Ctrl+C 會一併送達同一主控台的子程序，且 Job 物件關閉時會結束其中的程序。
+#[cfg(not(unix))]
+fn kill_child(_pid: u32) {}
@@ -1,6 +1,19 @@
+use clap::ValueEnum;
 use owo_colors::{OwoColorize, Style};
-use similar::{ChangeTag, TextDiff};
+use serde::{Deserialize, Serialize};
+use similar::{
+    Algorithm, ChangeTag, DiffOp, DiffTag, TextDiff, capture_diff_slices,
+    capture_diff_slices_deadline,
+};
 use std::borrow::Cow;
+use std::time::{Duration, Instant};
+use unicode_normalization::UnicodeNormalization;
+
+/// 輸出與答案皆為數值且不超過以下規模時，改以 token 為單位顯示差異。
+const TOKEN_DIFF_MAX_TOKENS: usize = 1000;
+const TOKEN_DIFF_MAX_LINE_LEN: usize = 200;
+/// 逐字差異的計算時間上限，超過時改以較粗略的結果顯示 (與 `iter_inline_changes` 相同)。
+const INLINE_DIFF_TIMEOUT: Duration = Duration::from_millis(500);
 
 struct TextChange<'a> {
     emphasized: bool,
@@ -15,27 +28,208 @@ fn to_unstyled_string(lines: &[TextChange]) -> String {
         .join("")
 }
 
-#[derive(Debug)]
+/// 已套用 ANSI 樣式的差異結果，序列化時保留樣式序列。
+#[derive(Debug, Clone, Serialize)]
 pub struct StyledDiff {
     pub output: String,
     pub answer: String,
+    /// 不同的行數；以 token 比對時為不同的 token 數。
+    pub mismatches: usize,
+    /// 產生差異時比對的輸出與答案，供 [`StyledDiff::inline`] 使用；以位元組比對時為 `None`。
+    #[serde(skip)]
+    compared: Option<ComparedText>,
+}
+
+impl StyledDiff {
+    /// 合併顯示的逐字差異，見 [`inline_diff_lines`]；只在需要顯示時計算，以位元組比對時為 `None`。
+    pub fn inline(&self) -> Option<String> {
+        self.compared.as_ref().map(|compared| {
+            inline_diff_lines(
+                &split_lines(&compared.output, compared.trim_trailing),
+                &split_lines(&compared.answer, compared.trim_trailing),
+            )
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ComparedText {
+    output: String,
+    answer: String,
+    trim_trailing: bool,
 }
 
 #[derive(Debug)]
 pub enum StyledComparison {
     Same,
+    /// 以空白分隔的 token 完全相同，僅空白或換行不同。
+    Presentation(StyledDiff),
     Diff(StyledDiff)
 }
 
-pub fn compare_styled(output: &str, answer: &str) -> StyledComparison {
-    let output_lines: Vec<&str> = output.trim_end().lines().map(str::trim_end).collect();
-    let answer_lines: Vec<&str> = answer.trim_end().lines().map(str::trim_end).collect();
+/// 判定輸出是否正確的方式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompareMode {
+    /// 輸出須與答案相同 (忽略行尾空白與結尾空行)。
+    #[default]
+    Exact,
+    /// 答案的每一行須依序出現在輸出中，中間可穿插其他行 (例如除錯訊息)。
+    /// 各行忽略行尾空白，空行不納入比對；不符合時仍以一般方式顯示差異。
+    Subsequence,
+}
+
+/// 答案的非空行是否依序出現在輸出的行中。
+pub fn contains_lines_in_order(output: &str, answer: &str) -> bool {
+    let mut output_lines = output.lines().map(str::trim_end);
+    answer
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .all(|expected| output_lines.any(|line| line == expected))
+}
+
+/// 比對前套用於輸出與答案的 Unicode 正規化形式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[value(rename_all = "UPPER")]
+pub enum UnicodeForm {
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+impl UnicodeForm {
+    pub fn normalize(self, text: &str) -> String {
+        match self {
+            Self::Nfc => text.nfc().collect(),
+            Self::Nfd => text.nfd().collect(),
+            Self::Nfkc => text.nfkc().collect(),
+            Self::Nfkd => text.nfkd().collect(),
+        }
+    }
+}
+
+/// 移除每一行開頭的空白，保留換行字元。
+pub fn trim_line_starts(text: &str) -> String {
+    text.split_inclusive('\n')
+        .map(|line| line.trim_start_matches(|c: char| c.is_whitespace() && c != '\n'))
+        .collect()
+}
+
+/// 逐位元組比對，符合任一答案即為 `Same`；否則以十六進位顯示與第一個答案的差異。
+pub fn compare_bytes_any(output: &[u8], answers: &[&[u8]]) -> StyledComparison {
+    if answers.contains(&output) {
+        return StyledComparison::Same;
+    }
+    let answer = answers.first().copied().unwrap_or_default();
+    let mismatches = output
+        .iter()
+        .zip(answer)
+        .filter(|(output_byte, answer_byte)| output_byte != answer_byte)
+        .count()
+        + output.len().abs_diff(answer.len());
+    StyledComparison::Diff(StyledDiff {
+        output: styled_hex_dump(output, answer, Style::new().red()),
+        answer: styled_hex_dump(answer, output, Style::new().green()),
+        mismatches,
+        compared: None,
+    })
+}
+
+/// 每行 16 個位元組的十六進位傾印。
+pub fn hex_dump(bytes: &[u8]) -> String {
+    styled_hex_dump(bytes, bytes, Style::new())
+}
+
+/// 同 [`hex_dump`]，與 `other` 不同的位元組以 `style` 標示。
+fn styled_hex_dump(bytes: &[u8], other: &[u8], style: Style) -> String {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let cells: Vec<String> = chunk
+                .iter()
+                .enumerate()
+                .map(|(column, byte)| {
+                    let cell = format!("{byte:02x}");
+                    if other.get(row * 16 + column) == Some(byte) {
+                        cell
+                    } else {
+                        cell.style(style).to_string()
+                    }
+                })
+                .collect();
+            format!("{:08x}  {}", row * 16, cells.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 取出前 `count` 行 (含換行字元)，不足 `count` 行時回傳整段文字。
+pub fn first_lines(text: &str, count: usize) -> &str {
+    if count == 0 {
+        return "";
+    }
+    match text.match_indices('\n').nth(count - 1) {
+        Some((index, _)) => &text[..=index],
+        None => text,
+    }
+}
+
+/// 與多個可接受的答案比對，符合任一即為 `Same`，其次為 `Presentation`；
+/// 否則回傳與最接近答案的差異。
+pub fn compare_styled_any(output: &str, answers: &[&str], trim_trailing: bool) -> StyledComparison {
+    let mut presentation = None;
+    for answer in answers {
+        match compare_styled(output, answer, trim_trailing) {
+            StyledComparison::Same => return StyledComparison::Same,
+            comparison @ StyledComparison::Presentation(_) if presentation.is_none() => {
+                presentation = Some(comparison);
+            }
+            _ => {}
+        }
+    }
+    if let Some(presentation) = presentation {
+        return presentation;
+    }
+
+    let closest = answers
+        .iter()
+        .copied()
+        .max_by(|a, b| {
+            let ratio_a = TextDiff::from_lines(output, *a).ratio();
+            let ratio_b = TextDiff::from_lines(output, *b).ratio();
+            ratio_a.total_cmp(&ratio_b)
+        })
+        .unwrap_or_default();
+
+    compare_styled(output, closest, trim_trailing)
+}
+
+/// 逐行比對輸出與答案，忽略結尾的空行；`trim_trailing` 為 `false` 時每行結尾的空白也須相同。
+pub fn compare_styled(output: &str, answer: &str, trim_trailing: bool) -> StyledComparison {
+    let output_lines = split_lines(output, trim_trailing);
+    let answer_lines = split_lines(answer, trim_trailing);
 
     if output_lines == answer_lines {
         return StyledComparison::Same;
     }
+    let same_tokens = output.split_whitespace().eq(answer.split_whitespace());
+
+    if prefers_token_diff(output, answer)
+        && let diff @ StyledComparison::Diff(_) = compare_token_styled(output, answer)
+    {
+        return diff;
+    }
 
+    let compared = ComparedText {
+        output: output.to_owned(),
+        answer: answer.to_owned(),
+        trim_trailing,
+    };
     let diff = TextDiff::from_slices(&output_lines, &answer_lines);
+    let mismatches = count_mismatches(diff.ops());
 
     let mut output = String::with_capacity(output.len());
     let mut answer = String::with_capacity(answer.len());
@@ -97,5 +291,312 @@ pub fn compare_styled(output: &str, answer: &str) -> StyledComparison {
     output.truncate(output.trim_end().len());
     answer.truncate(answer.trim_end().len());
 
-    StyledComparison::Diff(StyledDiff { output, answer })
+    let diff = StyledDiff {
+        output,
+        answer,
+        mismatches,
+        compared: Some(compared),
+    };
+    if same_tokens {
+        StyledComparison::Presentation(diff)
+    } else {
+        StyledComparison::Diff(diff)
+    }
+}
+
+/// 以空白分隔的 token 為單位比對，適合排序後的數列等純數值輸出；保留原本的換行與空白。
+pub fn compare_token_styled(output: &str, answer: &str) -> StyledComparison {
+    let output_tokens = split_tokens(output.trim_end());
+    let answer_tokens = split_tokens(answer.trim_end());
+    let output_words: Vec<&str> = output_tokens.iter().map(|(token, _)| *token).collect();
+    let answer_words: Vec<&str> = answer_tokens.iter().map(|(token, _)| *token).collect();
+
+    if output_words == answer_words {
+        return StyledComparison::Same;
+    }
+
+    let compared = ComparedText {
+        output: output.to_owned(),
+        answer: answer.to_owned(),
+        trim_trailing: true,
+    };
+    let mut output = String::with_capacity(output.len());
+    let mut answer = String::with_capacity(answer.len());
+
+    let ops = capture_diff_slices(Algorithm::Myers, &output_words, &answer_words);
+    let mismatches = count_mismatches(&ops);
+    for op in ops {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        let (output_style, answer_style) = match tag {
+            DiffTag::Equal => (None, None),
+            _ => (Some(Style::new().red()), Some(Style::new().green())),
+        };
+        push_tokens(&mut output, &output_tokens[old_range], output_style);
+        push_tokens(&mut answer, &answer_tokens[new_range], answer_style);
+    }
+
+    output.truncate(output.trim_end().len());
+    answer.truncate(answer.trim_end().len());
+
+    StyledComparison::Diff(StyledDiff {
+        output,
+        answer,
+        mismatches,
+        compared: Some(compared),
+    })
+}
+
+/// 類似 `git diff --word-diff`，將輸出與答案合併為一份：相同的行只顯示一次，
+/// 不同的行逐字元比對，輸出多出的部分以 `[-…-]` (紅) 標示，答案多出的部分以 `{+…+}` (綠) 標示。
+fn inline_diff_lines(output: &[&str], answer: &[&str]) -> String {
+    let deadline = Instant::now() + INLINE_DIFF_TIMEOUT;
+    let mut lines = Vec::new();
+    for op in capture_diff_slices_deadline(Algorithm::Myers, output, answer, Some(deadline)) {
+        let (_, old_range, new_range) = op.as_tag_tuple();
+        let old = &output[old_range];
+        let new = &answer[new_range];
+        // 被取代的區塊依序兩兩配對，多出的行整行標示
+        for index in 0..old.len().max(new.len()) {
+            lines.push(match (old.get(index), new.get(index)) {
+                (Some(old), Some(new)) => inline_line_diff(old, new, deadline),
+                (Some(old), None) => mark_change(ChangeTag::Delete, old),
+                (None, Some(new)) => mark_change(ChangeTag::Insert, new),
+                (None, None) => unreachable!(),
+            });
+        }
+    }
+    lines.join("\n")
+}
+
+fn inline_line_diff(output: &str, answer: &str, deadline: Instant) -> String {
+    let diff = TextDiff::configure()
+        .deadline(deadline)
+        .diff_chars(output, answer);
+    let mut line = String::with_capacity(output.len().max(answer.len()));
+    let mut run = String::new();
+    let mut run_tag = ChangeTag::Equal;
+    for change in diff.iter_all_changes() {
+        if change.tag() != run_tag {
+            line.push_str(&mark_change(run_tag, &run));
+            run.clear();
+            run_tag = change.tag();
+        }
+        run.push_str(change.value());
+    }
+    line.push_str(&mark_change(run_tag, &run));
+    line
+}
+
+fn mark_change(tag: ChangeTag, text: &str) -> String {
+    match tag {
+        _ if text.is_empty() => String::new(),
+        ChangeTag::Equal => text.to_owned(),
+        ChangeTag::Delete => format!("[-{text}-]").red().to_string(),
+        ChangeTag::Insert => format!("{{+{text}+}}").green().to_string(),
+    }
+}
+
+/// 每段不同之處以兩側較長者計算，例如一行被改寫計為 1、多出兩行計為 2。
+fn count_mismatches(ops: &[DiffOp]) -> usize {
+    ops.iter()
+        .map(|op| match op.as_tag_tuple() {
+            (DiffTag::Equal, _, _) => 0,
+            (_, old_range, new_range) => old_range.len().max(new_range.len()),
+        })
+        .sum()
+}
+
+/// 切成行並忽略結尾的空行；`trim_trailing` 為真時一併移除每行結尾的空白。
+fn split_lines(text: &str, trim_trailing: bool) -> Vec<&str> {
+    if trim_trailing {
+        text.trim_end().lines().map(str::trim_end).collect()
+    } else {
+        text.trim_end_matches(['\r', '\n']).lines().collect()
+    }
+}
+
+fn prefers_token_diff(output: &str, answer: &str) -> bool {
+    [output, answer].into_iter().all(|text| {
+        text.lines()
+            .all(|line| line.len() <= TOKEN_DIFF_MAX_LINE_LEN)
+            && text.split_whitespace().count() < TOKEN_DIFF_MAX_TOKENS
+            && text
+                .split_whitespace()
+                .all(|token| token.parse::<f64>().is_ok())
+    })
+}
+
+/// 將文字切成 (token, 其後的空白) 的序列。
+fn split_tokens(text: &str) -> Vec<(&str, &str)> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let token_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (token, after) = rest.split_at(token_end);
+        let space_end = after
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(after.len());
+        tokens.push((token, &after[..space_end]));
+        rest = &after[space_end..];
+    }
+    tokens
+}
+
+fn push_tokens(target: &mut String, tokens: &[(&str, &str)], style: Option<Style>) {
+    for (token, space) in tokens {
+        match style {
+            Some(style) => target.push_str(&token.style(style).to_string()),
+            None => target.push_str(token),
+        }
+        target.push_str(space);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_diff_highlights_single_number() {
+        let StyledComparison::Diff(diff) = compare_styled("1 2 3 4\n", "1 2 5 4\n", true) else {
+            panic!("expected a diff");
+        };
+        assert_eq!(
+            diff.output,
+            format!("1 2 {} 4", "3".style(Style::new().red()))
+        );
+        assert_eq!(
+            diff.answer,
+            format!("1 2 {} 4", "5".style(Style::new().green()))
+        );
+    }
+
+    #[test]
+    fn test_token_diff_keeps_line_layout() {
+        let StyledComparison::Diff(diff) = compare_token_styled("1\n2\n3", "1\n4\n3") else {
+            panic!("expected a diff");
+        };
+        assert_eq!(
+            diff.output,
+            format!("1\n{}\n3", "2".style(Style::new().red()))
+        );
+    }
+
+    #[test]
+    fn test_whitespace_only_difference_is_presentation() {
+        assert!(matches!(
+            compare_styled("1 2\n3\n", "1\n2 3\n", true),
+            StyledComparison::Presentation(_)
+        ));
+        assert!(matches!(
+            compare_styled_any("a  b", &["a c", "a b"], true),
+            StyledComparison::Presentation(_)
+        ));
+        assert!(matches!(
+            compare_styled("1 2", "1 3", true),
+            StyledComparison::Diff(_)
+        ));
+    }
+
+    #[test]
+    fn test_keep_trailing_whitespace() {
+        assert!(matches!(
+            compare_styled("1 2 \n3\n\n", "1 2\n3", true),
+            StyledComparison::Same
+        ));
+        assert!(matches!(
+            compare_styled("1 2 \n3\n\n", "1 2\n3", false),
+            StyledComparison::Presentation(_)
+        ));
+        assert!(matches!(
+            compare_styled("1 2\r\n3\n", "1 2\n3", false),
+            StyledComparison::Same
+        ));
+    }
+
+    #[test]
+    fn test_mismatch_count() {
+        let StyledComparison::Diff(diff) = compare_styled("1 2 3 4", "1 5 3 6", true) else {
+            panic!("expected a diff");
+        };
+        assert_eq!(diff.mismatches, 2);
+
+        let StyledComparison::Diff(diff) = compare_styled("a\nb\nc", "a\nx\nc\nd", true) else {
+            panic!("expected a diff");
+        };
+        assert_eq!(diff.mismatches, 2);
+    }
+
+    #[test]
+    fn test_trim_line_starts() {
+        assert_eq!(trim_line_starts("    5\n\t6 7\n\n  \n8"), "5\n6 7\n\n\n8");
+        assert!(matches!(
+            compare_styled(&trim_line_starts("  1\n   2"), "1\n2", true),
+            StyledComparison::Same
+        ));
+    }
+
+    #[test]
+    fn test_contains_lines_in_order() {
+        let output = "debug: start\n3\ndebug: mid\n4  \n";
+        assert!(contains_lines_in_order(output, "3\n4"));
+        assert!(contains_lines_in_order(output, "3\n\n4\n"));
+        assert!(!contains_lines_in_order(output, "4\n3"));
+        assert!(!contains_lines_in_order(output, "3\n5"));
+    }
+
+    #[test]
+    fn test_compare_bytes_any() {
+        assert!(matches!(
+            compare_bytes_any(&[0, 255, 10], &[b"x", &[0, 255, 10]]),
+            StyledComparison::Same
+        ));
+        let StyledComparison::Diff(diff) = compare_bytes_any(&[0, 1, 2], &[&[0, 9]]) else {
+            panic!("expected a diff");
+        };
+        assert_eq!(diff.mismatches, 2);
+        assert_eq!(
+            diff.answer,
+            format!("00000000  00 {}", "09".style(Style::new().green()))
+        );
+    }
+
+    #[test]
+    fn test_inline_diff_marks_changed_characters() {
+        let StyledComparison::Diff(diff) =
+            compare_styled("a\nYes 12\nextra\n", "a\nYes 13\n", true)
+        else {
+            panic!("expected a diff");
+        };
+        assert_eq!(
+            diff.inline().unwrap(),
+            format!(
+                "a\nYes 1{}{}\n{}",
+                "[-2-]".red(),
+                "{+3+}".green(),
+                "[-extra-]".red()
+            )
+        );
+    }
+
+    #[test]
+    fn test_first_lines() {
+        assert_eq!(first_lines("1\n2\n3\n", 2), "1\n2\n");
+        assert_eq!(first_lines("1\n2", 5), "1\n2");
+        assert_eq!(first_lines("1", 0), "");
+    }
+
+    #[test]
+    fn test_unicode_form_normalizes_combining_marks() {
+        let composed = "\u{e9}";
+        let decomposed = "e\u{301}";
+        assert_eq!(UnicodeForm::Nfc.normalize(decomposed), composed);
+        assert_eq!(UnicodeForm::Nfd.normalize(composed), decomposed);
+    }
+
+    #[test]
+    fn test_text_output_uses_line_diff() {
+        assert!(!prefers_token_diff("hello world", "hello there"));
+        assert!(prefers_token_diff("1 2.5 -3", "1 2 3"));
+    }
 }
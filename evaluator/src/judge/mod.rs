@@ -1,63 +1,256 @@
-use std::io::Write;
-use std::process::{Command, Stdio};
-use std::time::Instant;
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Write};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::judge::comparison::{StyledComparison, compare_styled};
+use crate::judge::comparison::{
+    StyledComparison, compare_bytes_any, compare_styled_any, contains_lines_in_order, first_lines,
+    hex_dump, trim_line_starts,
+};
 use crate::judge::verdict::{JudgeStatus, JudgeVerdict, Limitation};
-use crate::monitor::create_memory_monitor;
-use crate::utils::{PrettyNumber, center_text};
+use crate::interrupt::track_child;
+use crate::monitor::prepare_memory_monitor;
+use crate::phases::{self, Phase};
+use crate::reader::TestCase;
+use crate::sandbox::is_violation;
+use crate::utils::{PrettyNumber, center_text, display_width, truncate_styled};
 
 mod comparison;
+pub use comparison::{CompareMode, UnicodeForm};
 pub mod verdict;
 
 const INFO_SPACE: usize = 30;
+const MAX_INFO_SPACE: usize = 80;
 
-pub fn evaluate<'a>(
-    runner: &mut Command,
-    input: &'a str,
-    ans: &'a str,
-    limit: &Limitation,
-) -> JudgeVerdict<'a> {
-    let ans = ans.trim_end();
-    let mut verdict: JudgeVerdict<'a> = JudgeVerdict::new(input);
-
-    let mut child = runner
-        .stdin(Stdio::piped())
+/// 依設定進行 Unicode 正規化並移除行首空白，輸出與答案皆須經過相同處理。
+fn rewrite_text(text: &str, limit: &Limitation) -> String {
+    let text = match limit.unicode_form {
+        Some(form) => Cow::Owned(form.normalize(text)),
+        None => Cow::Borrowed(text),
+    };
+    if limit.ignore_leading_whitespace {
+        trim_line_starts(&text)
+    } else {
+        text.into_owned()
+    }
+}
+
+/// 移除答案結尾的換行；預設一併移除空白，保留行尾空白時最後一行的空白也須保留。
+fn trim_answer_end<'a>(answer: &'a str, limit: &Limitation) -> &'a str {
+    if limit.trim_trailing {
+        answer.trim_end()
+    } else {
+        answer.trim_end_matches(['\r', '\n'])
+    }
+}
+
+/// 第一次重試前的等待時間，之後每次加倍。
+const SPAWN_BACKOFF: Duration = Duration::from_millis(10);
+
+/// 啟動受測程式，因系統資源暫時不足 (如程序數達到上限) 而失敗時最多重試 `retries` 次。
+fn spawn_with_retry(runner: &mut Command, retries: u32) -> io::Result<Child> {
+    let mut backoff = SPAWN_BACKOFF;
+    let mut attempt = 0;
+    loop {
+        match runner.spawn() {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock && attempt < retries => {
+                log::debug!("spawn failed ({e}), retrying in {backoff:?}");
+                thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// 依 `limit` 取得實際寫入受測程式的輸入。
+pub fn prepare_input<'a>(case: &'a TestCase, limit: &Limitation) -> Cow<'a, [u8]> {
+    let input = if limit.binary_io {
+        case.input_bytes()
+    } else {
+        case.input.as_bytes()
+    };
+    // 預設補上結尾換行，避免逐行讀取的程式讀不到最後一行
+    if limit.binary_io || limit.exact_input || input.is_empty() || input.ends_with(b"\n") {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned([input, b"\n"].concat())
+    }
+}
+
+pub fn evaluate<'a>(runner: &mut Command, case: &'a TestCase, limit: &Limitation) -> JudgeVerdict<'a> {
+    let answers = case.answers();
+    let normalized_answers: Option<Vec<String>> = limit
+        .rewrites_text()
+        .then(|| answers.iter().map(|ans| rewrite_text(ans, limit)).collect());
+    let mut answers: Vec<&str> = match &normalized_answers {
+        Some(normalized) => normalized.iter().map(|ans| trim_answer_end(ans, limit)).collect(),
+        None => answers.iter().map(|ans| trim_answer_end(ans, limit)).collect(),
+    };
+    if let Some(count) = limit.compare_lines {
+        for answer in &mut answers {
+            *answer = trim_answer_end(first_lines(answer, count), limit);
+        }
+    }
+    let mut verdict: JudgeVerdict<'a> = JudgeVerdict::new(if limit.binary_io {
+        case.input_bytes()
+    } else {
+        case.input.as_bytes()
+    });
+    let input = prepare_input(case, limit);
+
+    if let Some(path) = &limit.input_file
+        && let Err(e) = fs::write(path, &input)
+    {
+        verdict.status(JudgeStatus::RE(format!(
+            "無法寫入輸入檔 {}: {e}",
+            path.display()
+        )));
+        return verdict;
+    }
+    if let Some(path) = &limit.output_file {
+        // 避免沿用上一筆測資留下的輸出檔
+        let _ = fs::remove_file(path);
+    }
+
+    let monitor_start = Instant::now();
+    let start_monitor = prepare_memory_monitor(limit.max_processes, limit.memory_metric);
+    let mut monitor_time = monitor_start.elapsed();
+
+    runner
+        .stdin(if limit.input_file.is_some() {
+            Stdio::null()
+        } else {
+            Stdio::piped()
+        })
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .expect("無法啟動執行檔");
+        .stderr(Stdio::piped());
+    // 自成一個程序群組，讓 Ctrl+C 能一併結束受測程式產生的子程序
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(runner, 0);
+    let mut child = match spawn_with_retry(runner, limit.spawn_retries) {
+        Ok(child) => child,
+        Err(e) => {
+            verdict.status(JudgeStatus::RE(format!("無法啟動執行檔: {e}")));
+            return verdict;
+        }
+    };
 
     let start_time = Instant::now();
 
     let pid = child.id();
+    log::debug!("spawned child process {pid}");
+    track_child(Some(pid));
 
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin.write_all(input.as_bytes()).unwrap();
-    }
+    let monitor_start = Instant::now();
+    let get_monitor_report = start_monitor(pid);
+    monitor_time += monitor_start.elapsed();
 
-    let get_memory_usage = create_memory_monitor(pid);
+    // 程式提前結束 (啟動即崩潰或被 Ctrl+C 終止) 時會關閉 stdin，此時照常依執行結果評測
+    let stdin_error = child
+        .stdin
+        .take()
+        .and_then(|mut stdin| stdin.write_all(&input).err())
+        .filter(|e| e.kind() != io::ErrorKind::BrokenPipe);
 
     let output_result = child.wait_with_output();
+    track_child(None);
 
     let elapsed_time = start_time.elapsed();
-    let memory_usage_option = get_memory_usage();
+    let monitor_start = Instant::now();
+    let monitor_report = get_monitor_report();
+    monitor_time += monitor_start.elapsed();
+    phases::add(Phase::Monitor, monitor_time);
+    let memory_usage_option = monitor_report.memory;
 
     verdict.duration(Some(elapsed_time));
     verdict.memory(memory_usage_option);
 
+    if let Some(e) = stdin_error {
+        verdict.status(JudgeStatus::RE(format!("無法寫入輸入: {e}")));
+        return verdict;
+    }
+
     match output_result {
+        Ok(output) if limit.sandbox && is_violation(&output.status) => {
+            verdict.status(JudgeStatus::SV);
+        }
+        Ok(_) if monitor_report.process_limit_exceeded => {
+            verdict.status(JudgeStatus::RE(format!(
+                "超過程序數量限制 ({})",
+                limit.max_processes.unwrap_or_default()
+            )));
+        }
+        Ok(_) if limit.output_file.as_ref().is_some_and(|path| !path.is_file()) => {
+            verdict.status(JudgeStatus::RE(format!(
+                "程式未產生輸出檔 {}",
+                limit.output_file.as_ref().unwrap().display()
+            )));
+        }
         Ok(output) => {
-            let actual_output = String::from_utf8_lossy(&output.stdout);
-            match compare_styled(&actual_output, ans) {
+            let raw_output: Cow<[u8]> = match &limit.output_file {
+                Some(path) => Cow::Owned(fs::read(path).unwrap_or_default()),
+                None => Cow::Borrowed(&output.stdout),
+            };
+            let mut actual_output = String::from_utf8_lossy(&raw_output);
+            if limit.rewrites_text() {
+                actual_output = rewrite_text(&actual_output, limit).into();
+            }
+            if let Some(count) = limit.compare_lines {
+                actual_output = match actual_output {
+                    Cow::Borrowed(output) => Cow::Borrowed(first_lines(output, count)),
+                    Cow::Owned(output) => Cow::Owned(first_lines(&output, count).to_owned()),
+                };
+            }
+            let comparison = if limit.binary_io {
+                compare_bytes_any(&raw_output, &case.answer_bytes())
+            } else if limit.compare_mode == CompareMode::Subsequence
+                && answers
+                    .iter()
+                    .any(|answer| contains_lines_in_order(&actual_output, answer))
+            {
+                StyledComparison::Same
+            } else {
+                compare_styled_any(&actual_output, &answers, limit.trim_trailing)
+            };
+            match comparison {
                 StyledComparison::Same => {
                     verdict.status(JudgeStatus::AC);
+                    if limit.show_output {
+                        verdict.output(actual_output.into_owned());
+                    }
+                }
+                StyledComparison::Presentation(diff) | StyledComparison::Diff(diff)
+                    if limit.max_mismatch.is_some_and(|max| diff.mismatches <= max) =>
+                {
+                    verdict.mismatches(diff.mismatches);
+                    verdict.status(JudgeStatus::AC);
+                    if limit.show_output {
+                        verdict.output(actual_output.into_owned());
+                    }
+                }
+                StyledComparison::Presentation(diff) if limit.presentation_error => {
+                    verdict.mismatches(diff.mismatches);
+                    verdict.status(JudgeStatus::PE(diff));
                 }
-                StyledComparison::Diff(diff) => {
+                StyledComparison::Presentation(diff) | StyledComparison::Diff(diff) => {
+                    verdict.mismatches(diff.mismatches);
                     if !output.stderr.is_empty() {
                         verdict.status(JudgeStatus::RE(
                             String::from_utf8_lossy(&output.stderr).into(),
                         ))
+                    } else if limit.detect_empty_output
+                        && actual_output.trim().is_empty()
+                        && answers.iter().all(|ans| !ans.is_empty())
+                        && !output.status.success()
+                    {
+                        verdict.status(JudgeStatus::RE(format!(
+                            "程式未產生任何輸出 ({})",
+                            output.status
+                        )))
                     } else {
                         verdict.status(JudgeStatus::WA(diff));
                     }
@@ -84,30 +277,88 @@ pub fn evaluate<'a>(
     verdict
 }
 
-pub fn print_test_label(round: u32) {
+pub fn print_test_label(round: usize) {
     println!(
         "{}\n",
         center_text(&format!("Test {round}"), INFO_SPACE, "_")
     );
 }
 
-pub fn print_test_info(verdict: &JudgeVerdict, limit: &Limitation) {
+/// 評測結果的顯示設定。
+pub struct ReportOptions {
+    /// WA 差異區塊最多輸出的位元組數 (含 ANSI 樣式序列)。
+    pub max_diff_bytes: usize,
+    /// AC 時也顯示程式輸出。
+    pub show_output: bool,
+    /// WA 時以合併的逐字差異取代輸出與答案兩個區塊。
+    pub explain_diff: bool,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            max_diff_bytes: 64 * 1024,
+            show_output: false,
+            explain_diff: false,
+        }
+    }
+}
+
+pub fn print_test_info(verdict: &JudgeVerdict, limit: &Limitation, options: &ReportOptions) {
     match &verdict.status {
-        JudgeStatus::AC => println!("✅ [AC] 答案正確！"),
+        JudgeStatus::AC => {
+            if let Some(mismatches) = verdict.mismatches {
+                println!("✅ [AC] 差異 {mismatches} 處，未超過容許上限！");
+            } else {
+                println!("✅ [AC] 答案正確！");
+            }
+            if options.show_output
+                && let Some(output) = &verdict.output
+            {
+                let frame = frame_width([output.as_str()]);
+                let section = format!(
+                    "\n{}\n{}",
+                    center_text("Program Output", frame, "-"),
+                    output.trim_end()
+                );
+                println!("{}", truncate_styled(&section, options.max_diff_bytes));
+            }
+        }
         JudgeStatus::RE(msg) => println!("❌ [RE] {msg}"),
         JudgeStatus::Tle(_) => println!("❌ [TLE] 程式執行時間超過限制！"),
         JudgeStatus::Mle(_) => println!("❌ [MLE] 程式記憶體使用量超過限制！"),
-        JudgeStatus::WA(diff) => {
-            println!("❌ [WA] 答案比對失敗！");
-            println!(
-                "\n{}\n{}\n\n{}\n{}\n{}\n{}\n",
-                center_text("Input", INFO_SPACE, "-"),
-                verdict.input,
-                center_text("Program Output", INFO_SPACE, "-"),
-                diff.output,
-                center_text("Expect Output", INFO_SPACE, "-"),
-                diff.answer
-            );
+        JudgeStatus::SV => println!("❌ [SV] 程式呼叫了沙箱禁止的系統呼叫！"),
+        JudgeStatus::WA(diff) | JudgeStatus::PE(diff) => {
+            if matches!(verdict.status, JudgeStatus::PE(_)) {
+                println!("❌ [PE] 輸出內容正確，但空白或換行格式不符！");
+            } else {
+                println!("❌ [WA] 答案比對失敗！(差異 {} 處)", diff.mismatches);
+            }
+            let input = display_input(verdict.input, limit, options);
+            let section = if options.explain_diff
+                && let Some(inline) = diff.inline()
+            {
+                let frame = frame_width([&*input, &inline]);
+                format!(
+                    "\n{}\n{}\n\n{}\n{}\n",
+                    center_text("Input", frame, "-"),
+                    input,
+                    center_text("Diff ([-Program-] {+Expect+})", frame, "-"),
+                    inline
+                )
+            } else {
+                let frame = frame_width([&*input, &diff.output, &diff.answer]);
+                format!(
+                    "\n{}\n{}\n\n{}\n{}\n{}\n{}\n",
+                    center_text("Input", frame, "-"),
+                    input,
+                    center_text("Program Output", frame, "-"),
+                    diff.output,
+                    center_text("Expect Output", frame, "-"),
+                    diff.answer
+                )
+            };
+            println!("{}", truncate_styled(&section, options.max_diff_bytes));
         }
     };
 
@@ -134,3 +385,23 @@ pub fn print_test_info(verdict: &JudgeVerdict, limit: &Limitation) {
         );
     }
 }
+
+/// 以位元組評測時只傾印會被顯示的前 `max_diff_bytes` 個位元組，避免為大型輸入建立完整的傾印。
+fn display_input<'a>(input: &'a [u8], limit: &Limitation, options: &ReportOptions) -> Cow<'a, str> {
+    if limit.binary_io {
+        Cow::Owned(hex_dump(&input[..input.len().min(options.max_diff_bytes)]))
+    } else {
+        String::from_utf8_lossy(input)
+    }
+}
+
+/// 依內容最寬一行的顯示寬度決定分隔線長度，使含全形字元的區塊也能對齊。
+fn frame_width<'a>(blocks: impl IntoIterator<Item = &'a str>) -> usize {
+    blocks
+        .into_iter()
+        .flat_map(str::lines)
+        .map(display_width)
+        .max()
+        .unwrap_or(0)
+        .clamp(INFO_SPACE, MAX_INFO_SPACE)
+}
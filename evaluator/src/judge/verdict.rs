@@ -1,18 +1,127 @@
 use std::borrow::Cow;
 use std::fmt;
+use std::path::PathBuf;
 use std::time::Duration;
 
+use clap::ValueEnum;
 use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
 
-use crate::judge::comparison::StyledDiff;
+use crate::judge::comparison::{CompareMode, StyledDiff, UnicodeForm};
+use crate::monitor::MemoryMetric;
 use crate::utils::PrettyNumber;
 
+#[derive(Debug, Clone, Serialize)]
 pub struct Limitation {
     pub(super) max_memory: Option<usize>,
     pub(super) max_time: Option<Duration>,
+    pub(super) detect_empty_output: bool,
+    pub(super) sandbox: bool,
+    pub(super) max_processes: Option<usize>,
+    pub(super) unicode_form: Option<UnicodeForm>,
+    pub(super) ignore_leading_whitespace: bool,
+    pub(super) trim_trailing: bool,
+    pub(super) exact_input: bool,
+    pub(super) binary_io: bool,
+    pub(super) presentation_error: bool,
+    pub(super) input_file: Option<PathBuf>,
+    pub(super) output_file: Option<PathBuf>,
+    pub(super) memory_metric: MemoryMetric,
+    pub(super) compare_lines: Option<usize>,
+    pub(super) compare_mode: CompareMode,
+    pub(super) max_mismatch: Option<usize>,
+    pub(super) spawn_retries: u32,
+    pub(super) show_output: bool,
 }
 
 impl Limitation {
+    /// 預期有輸出但程式無輸出且異常結束時，判為 RE 而非 WA。
+    pub fn detect_empty_output(&mut self, enabled: bool) -> &mut Self {
+        self.detect_empty_output = enabled;
+        self
+    }
+    /// 受測程式在沙箱中執行，因禁止的系統呼叫被終止時判為 SV。
+    pub fn sandbox(&mut self, enabled: bool) -> &mut Self {
+        self.sandbox = enabled;
+        self
+    }
+    /// 程序的數量上限，超過時判為 RE；Linux 的執行緒也計入 (macOS 不支援)。
+    pub fn max_processes(&mut self, max_processes: Option<usize>) -> &mut Self {
+        self.max_processes = max_processes;
+        self
+    }
+    /// 比對前將輸出與答案轉為指定的 Unicode 正規化形式。
+    pub fn unicode_form(&mut self, form: Option<UnicodeForm>) -> &mut Self {
+        self.unicode_form = form;
+        self
+    }
+    /// 比對前移除每一行開頭的空白。
+    pub fn ignore_leading_whitespace(&mut self, enabled: bool) -> &mut Self {
+        self.ignore_leading_whitespace = enabled;
+        self
+    }
+    /// 比對前移除每一行結尾的空白 (預設開啟)；關閉時行尾空白也須與答案相同。
+    pub fn trim_trailing(&mut self, enabled: bool) -> &mut Self {
+        self.trim_trailing = enabled;
+        self
+    }
+    /// 原樣寫入測資輸入；預設會在缺少結尾換行時補上一個。
+    pub fn exact_input(&mut self, enabled: bool) -> &mut Self {
+        self.exact_input = enabled;
+        self
+    }
+    /// 原樣寫入輸入並逐位元組比對輸出與答案。
+    pub fn binary_io(&mut self, enabled: bool) -> &mut Self {
+        self.binary_io = enabled;
+        self
+    }
+    /// 比對前是否需要改寫輸出與答案的內容。
+    pub(super) fn rewrites_text(&self) -> bool {
+        self.unicode_form.is_some() || self.ignore_leading_whitespace
+    }
+    /// 輸出僅空白或換行與答案不同時判為 PE 而非 WA。
+    pub fn presentation_error(&mut self, enabled: bool) -> &mut Self {
+        self.presentation_error = enabled;
+        self
+    }
+    /// 將輸入寫入此檔案，而非由 stdin 餵入。
+    pub fn input_file(&mut self, path: Option<PathBuf>) -> &mut Self {
+        self.input_file = path;
+        self
+    }
+    /// AC 時保留程式輸出以供顯示；關閉時不複製輸出。
+    pub fn show_output(&mut self, enabled: bool) -> &mut Self {
+        self.show_output = enabled;
+        self
+    }
+    /// 從此檔案讀取程式輸出，而非 stdout。
+    pub fn output_file(&mut self, path: Option<PathBuf>) -> &mut Self {
+        self.output_file = path;
+        self
+    }
+    /// 只比對輸出與答案的前 `count` 行，忽略其後的內容。
+    pub fn compare_lines(&mut self, count: Option<usize>) -> &mut Self {
+        self.compare_lines = count;
+        self
+    }
+    pub fn compare_mode(&mut self, mode: CompareMode) -> &mut Self {
+        self.compare_mode = mode;
+        self
+    }
+    /// 不同之處不超過 `max` 時仍判為 AC，用於寬鬆評分。
+    pub fn max_mismatch(&mut self, max: Option<usize>) -> &mut Self {
+        self.max_mismatch = max;
+        self
+    }
+    /// 啟動受測程式因系統資源暫時不足而失敗時的重試次數。
+    pub fn spawn_retries(&mut self, retries: u32) -> &mut Self {
+        self.spawn_retries = retries;
+        self
+    }
+    pub fn memory_metric(&mut self, metric: MemoryMetric) -> &mut Self {
+        self.memory_metric = metric;
+        self
+    }
     pub fn max_memory(&mut self, max_memory: Option<usize>) -> &mut Self {
         self.max_memory = max_memory;
         self
@@ -28,25 +137,48 @@ impl Default for Limitation {
         Self {
             max_memory: Some(1024 * 1024),
             max_time: Some(Duration::from_secs(2)),
+            detect_empty_output: true,
+            sandbox: false,
+            max_processes: None,
+            unicode_form: None,
+            ignore_leading_whitespace: false,
+            trim_trailing: true,
+            exact_input: false,
+            binary_io: false,
+            presentation_error: false,
+            input_file: None,
+            output_file: None,
+            memory_metric: MemoryMetric::default(),
+            compare_lines: None,
+            compare_mode: CompareMode::default(),
+            max_mismatch: None,
+            spawn_retries: 3,
+            show_output: false,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct JudgeVerdict<'a> {
     pub status: JudgeStatus,
-    pub input: &'a str,
+    /// 測資的原始輸入，以位元組評測時於顯示時才轉為十六進位傾印。
+    pub input: &'a [u8],
     pub duration: Option<Duration>,
     pub memory: Option<usize>,
+    pub output: Option<String>,
+    /// 輸出與答案不同之處的數量，完全相同時為 `None`。
+    pub mismatches: Option<usize>,
 }
 
 impl<'a> JudgeVerdict<'a> {
-    pub fn new(input: &'a str) -> Self {
+    pub fn new(input: &'a [u8]) -> Self {
         Self {
             status: JudgeStatus::RE("Failed".to_owned()),
             input,
             duration: None,
             memory: None,
+            output: None,
+            mismatches: None,
         }
     }
     pub fn is_accept(&self) -> bool {
@@ -61,9 +193,15 @@ impl<'a> JudgeVerdict<'a> {
     pub(super) fn memory(&mut self, memory: Option<usize>) {
         self.memory = memory;
     }
+    pub(super) fn output(&mut self, output: String) {
+        self.output = Some(output);
+    }
+    pub(super) fn mismatches(&mut self, mismatches: usize) {
+        self.mismatches = Some(mismatches);
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub enum JudgeStatus {
     /// Accept
     AC,
@@ -71,10 +209,41 @@ pub enum JudgeStatus {
     RE(String),
     /// Wrong Answer
     WA(StyledDiff),
+    /// Presentation Error
+    PE(StyledDiff),
     /// Time Limit Exceeded
     Tle(Duration),
     /// Memory Limit Exceeded
     Mle(usize),
+    /// Security Violation
+    SV,
+}
+
+/// 不含附帶資料的評測結果種類，用於比對預期結果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[value(rename_all = "UPPER")]
+pub enum VerdictKind {
+    AC,
+    RE,
+    WA,
+    PE,
+    Tle,
+    Mle,
+    SV,
+}
+
+impl fmt::Display for VerdictKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::AC => write!(f, "AC"),
+            Self::RE => write!(f, "RE"),
+            Self::WA => write!(f, "WA"),
+            Self::PE => write!(f, "PE"),
+            Self::Tle => write!(f, "TLE"),
+            Self::Mle => write!(f, "MLE"),
+            Self::SV => write!(f, "SV"),
+        }
+    }
 }
 
 impl JudgeStatus {
@@ -82,20 +251,36 @@ impl JudgeStatus {
         matches!(self, Self::AC)
     }
 
+    pub fn kind(&self) -> VerdictKind {
+        match self {
+            Self::AC => VerdictKind::AC,
+            Self::RE(_) => VerdictKind::RE,
+            Self::WA(_) => VerdictKind::WA,
+            Self::PE(_) => VerdictKind::PE,
+            Self::Tle(_) => VerdictKind::Tle,
+            Self::Mle(_) => VerdictKind::Mle,
+            Self::SV => VerdictKind::SV,
+        }
+    }
+
     pub fn to_str_short(&self) -> &str {
         match self {
             Self::RE(_) => "運行時錯誤 RE",
             Self::WA(_) => "答案錯誤 WA",
+            Self::PE(_) => "格式錯誤 PE",
             Self::Tle(_) => "超時錯誤 TLE",
             Self::Mle(_) => "記憶體超限 MLE",
+            Self::SV => "安全性違規 SV",
             Self::AC => "答案正確 AC",
         }
     }
 
     pub(crate) fn severity(&self) -> u8 {
         match self {
-            Self::RE(_) => 4,
-            Self::WA(_) => 3,
+            Self::SV => 6,
+            Self::RE(_) => 5,
+            Self::WA(_) => 4,
+            Self::PE(_) => 3,
             Self::Tle(_) => 2,
             Self::Mle(_) => 1,
             Self::AC => 0,
@@ -142,6 +327,14 @@ pub struct SummaryInfo {
     pub current_rounds: usize,
     pub total_time: Duration,
     pub total_memory: usize,
+    /// 執行最久的測資編號與用時，不論結果。
+    pub slowest: Option<(usize, Duration)>,
+    /// 答案正確的測資中執行最久者的編號與用時。
+    pub slowest_accepted: Option<(usize, Duration)>,
+    /// 記憶體用量最高的測資編號與用量 (KiB)。
+    pub peak_memory: Option<(usize, usize)>,
+    /// 所有測資輸出與答案不同之處的總數。
+    pub total_mismatches: usize,
     worse_status: JudgeStatus,
 }
 
@@ -152,20 +345,38 @@ impl Default for SummaryInfo {
             current_rounds: 0,
             total_time: Duration::ZERO,
             total_memory: 0,
+            slowest: None,
+            slowest_accepted: None,
+            peak_memory: None,
+            total_mismatches: 0,
             worse_status: JudgeStatus::AC,
         }
     }
 }
 
 impl SummaryInfo {
-    pub fn update(&mut self, verdict: JudgeVerdict) {
+    pub fn update(&mut self, case_number: usize, verdict: JudgeVerdict) {
         self.current_rounds += 1;
         if let Some(duration) = verdict.duration {
             self.total_time += duration;
+            if self.slowest.is_none_or(|(_, slowest)| duration > slowest) {
+                self.slowest = Some((case_number, duration));
+            }
+            if verdict.is_accept()
+                && self
+                    .slowest_accepted
+                    .is_none_or(|(_, slowest)| duration > slowest)
+            {
+                self.slowest_accepted = Some((case_number, duration));
+            }
         }
         if let Some(memory) = verdict.memory {
             self.total_memory += memory;
+            if self.peak_memory.is_none_or(|(_, peak)| memory > peak) {
+                self.peak_memory = Some((case_number, memory));
+            }
         }
+        self.total_mismatches += verdict.mismatches.unwrap_or_default();
         if verdict.is_accept() {
             self.success_rounds += 1;
         } else if verdict.status.is_severe_than(&self.worse_status) {
@@ -173,14 +384,41 @@ impl SummaryInfo {
         }
     }
     pub fn score(&self) -> usize {
-        self.success_rounds * 100 / self.current_rounds
+        self.success_rounds * 100 / self.current_rounds.max(1)
+    }
+    pub fn worst_status(&self) -> &JudgeStatus {
+        &self.worse_status
     }
 }
 
 impl fmt::Display for SummaryInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_status(f)?;
+        if let Some((case_number, duration)) = self.slowest {
+            write!(
+                f,
+                "，最慢: 測資 {case_number} ({} ms)",
+                duration.as_millis().prettify()
+            )?;
+        }
+        // 最慢的測資未通過時，另外列出通過的測資中最慢者
+        if let Some((case_number, duration)) = self.slowest_accepted
+            && self.slowest != self.slowest_accepted
+        {
+            write!(
+                f,
+                "，最慢 AC: 測資 {case_number} ({} ms)",
+                duration.as_millis().prettify()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl SummaryInfo {
+    fn fmt_status(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self.worse_status {
-            status @ JudgeStatus::WA(_) => {
+            status @ (JudgeStatus::WA(_) | JudgeStatus::PE(_)) => {
                 write!(
                     f,
                     "{} (score: {}%)",
@@ -205,8 +443,8 @@ impl fmt::Display for SummaryInfo {
                 f,
                 "{} ({} ms, {} KiB)",
                 JudgeStatus::AC.to_str_short().bright_green(),
-                self.total_time.as_millis() / self.current_rounds as u128,
-                self.total_memory / self.current_rounds
+                self.total_time.as_millis() / self.current_rounds.max(1) as u128,
+                self.total_memory / self.current_rounds.max(1)
             ),
             status => write!(f, "{}", status.to_str_short()),
         }
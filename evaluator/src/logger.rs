@@ -1,15 +1,34 @@
 use simplelog::*;
+use std::fs::File;
+use std::io;
+use std::path::Path;
 
-pub fn init_logger(level: LevelFilter) {
+/// 初始化終端機記錄器；若提供 `log_file`，另以 `file_level` 將記錄寫入該檔案。
+pub fn init_logger(level: LevelFilter, log_file: Option<(&Path, LevelFilter)>) -> io::Result<()> {
     let mut builder = ConfigBuilder::new();
     builder
         .set_thread_level(LevelFilter::Off)
         .set_time_level(LevelFilter::Off);
-    TermLogger::init(
+
+    let mut loggers: Vec<Box<dyn SharedLogger>> = vec![TermLogger::new(
         level,
         builder.build(),
         TerminalMode::Stderr,
         ColorChoice::Auto,
-    )
-    .unwrap();
+    )];
+
+    if let Some((path, file_level)) = log_file {
+        let mut file_builder = ConfigBuilder::new();
+        file_builder
+            .set_thread_level(LevelFilter::Off)
+            .set_time_level(LevelFilter::Error);
+        loggers.push(WriteLogger::new(
+            file_level,
+            file_builder.build(),
+            File::create(path)?,
+        ));
+    }
+
+    CombinedLogger::init(loggers).unwrap();
+    Ok(())
 }
@@ -13,33 +13,53 @@
 
 mod compile;
 mod config;
+mod history;
+mod interrupt;
 mod judge;
 mod logger;
 mod reader;
 mod monitor;
+mod phases;
+mod profile;
+mod sandbox;
 mod utils;
+mod validator;
 
-use std::process::{self, Command};
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::sync::atomic::Ordering;
+use std::time::Instant;
 
-use compile::prepare_command;
+use compile::{CompileOptions, prepare_command};
+use history::{CaseVerdict, load_verdicts, save_verdicts};
+use interrupt::{install_handler, is_interrupted, track_child};
 use judge::{
-    evaluate, print_test_info, print_test_label,
-    verdict::{CompileError, Limitation, SummaryInfo},
+    ReportOptions, evaluate, prepare_input, print_test_info, print_test_label,
+    verdict::{CompileError, Limitation, SummaryInfo, VerdictKind},
 };
+use monitor::install_cgroup_hook;
+use phases::{Phase, record, timed};
 use prettytable::{
     Cell, Row, Table,
     format::{FormatBuilder, LinePosition, LineSeparator},
 };
-use reader::{TestInfo, resolve_args};
-use utils::PrettyNumber;
+use profile::run_profiler;
+use reader::{BellMode, TestCase, TestInfo, resolve_args};
+use sandbox::apply_sandbox;
+use utils::{PrettyNumber, case_order, center_text};
+use validator::check_cases;
 
 use crate::{
-    config::TEMP_DIR,
+    config::{NUMBER_GROUPING, TEMP_DIR},
     reader::{EvaluatorConfig, ensure_dir_exists, read_config},
 };
 
 fn main() {
-    let mut info = resolve_args().unwrap_or_else(|e| {
+    let run_start = Instant::now();
+    let infos = resolve_args().unwrap_or_else(|e| {
         println!("❌ [SE] {e}");
         process::exit(1);
     });
@@ -48,28 +68,177 @@ fn main() {
         println!("❌ [SE] {e}");
         process::exit(1);
     });
-    info.with_config(&config);
+    record(Phase::ReadConfig, run_start);
 
     ensure_dir_exists(TEMP_DIR.as_path()).unwrap();
+    install_handler();
 
-    let Some(runner) = compile_source_code(&info, &config) else {
+    let batch = infos.len() > 1;
+    let bell = infos.first().and_then(|info| info.bell);
+    let profile_phases = infos.first().is_some_and(|info| info.profile_phases);
+    let mut outcomes = Vec::new();
+    for mut info in infos {
+        info.with_config(&config);
+        if let Some(grouping) = info.number_grouping {
+            NUMBER_GROUPING.store(grouping, Ordering::Relaxed);
+        }
+
+        if batch {
+            println!("\n📂 {}", info.file);
+        }
+        let file = info.file.clone();
+        let keep_going = info.keep_going;
+        let outcome = run_file(info, &config);
+        let stop = outcome.is_failure() && !keep_going;
+        outcomes.push((file, outcome));
+        if stop || is_interrupted() {
+            break;
+        }
+    }
+
+    if batch {
+        println!("\n📦 批次總覽:");
+        for (file, outcome) in &outcomes {
+            match outcome {
+                FileOutcome::Judged(summary_info, _) => println!("  {file}: {summary_info}"),
+                FileOutcome::Done => println!("  {file}: 完成"),
+                FileOutcome::CompileFailed => println!("  {file}: 編譯失敗 CE"),
+            }
+        }
+    }
+
+    if profile_phases {
+        print_phase_profile(run_start);
+    }
+    if let Some(mode) = bell {
+        ring_bell(mode, &outcomes);
+    }
+
+    if is_interrupted() {
+        process::exit(130);
+    }
+    if outcomes.iter().any(|(_, outcome)| outcome.is_failure()) {
         process::exit(1);
+    }
+}
+
+fn ring_bell(mode: BellMode, outcomes: &[(String, FileOutcome)]) {
+    let failed = outcomes.iter().any(|(_, outcome)| match outcome {
+        FileOutcome::Judged(summary_info, as_expected) => {
+            !as_expected || !summary_info.worst_status().is_accept()
+        }
+        FileOutcome::Done => false,
+        FileOutcome::CompileFailed => true,
+    });
+    let mut stdout = io::stdout();
+    if (mode == BellMode::Always || failed) && stdout.is_terminal() {
+        print!("\x07");
+        let _ = stdout.flush();
+    }
+}
+
+/// 單一原始碼檔案的處理結果。
+enum FileOutcome {
+    /// 已完成評測，附帶總結與結果是否符合 `--expect`。
+    Judged(Box<SummaryInfo>, bool),
+    /// 已執行、僅編譯或僅驗證測資。
+    Done,
+    CompileFailed,
+}
+
+impl FileOutcome {
+    fn is_failure(&self) -> bool {
+        matches!(self, Self::CompileFailed | Self::Judged(_, false))
+    }
+}
+
+fn run_file(info: TestInfo, config: &EvaluatorConfig) -> FileOutcome {
+    if info.check_inputs {
+        check_inputs(&info);
+        return FileOutcome::Done;
+    }
+
+    let Some(mut runner) = compile_source_code(&info, config) else {
+        return FileOutcome::CompileFailed;
     };
+    if info.compile_only {
+        println!("✅ 編譯成功");
+        return FileOutcome::Done;
+    }
+    runner.args(&info.extra_args);
+    install_runner_hooks(&mut runner, info.sandbox);
 
     log::debug!("runner: {runner:?}");
 
     if info.do_judge {
-        judge(info, runner);
+        let expected_verdict = info.expected_verdict;
+        let summary_info = judge(info, runner);
+
+        let mut as_expected = true;
+        if let Some(expected) = expected_verdict
+            && !is_interrupted()
+        {
+            let actual = summary_info.worst_status().kind();
+            if actual == expected {
+                println!("🧪 評測結果符合預期 ({expected})");
+            } else {
+                println!("❌ 評測結果不符預期: 預期 {expected}，實際 {actual}");
+                as_expected = false;
+            }
+        }
+        FileOutcome::Judged(Box::new(summary_info), as_expected)
     } else {
-        execute(runner);
+        execute(runner, info.stdin_prompt);
+        FileOutcome::Done
+    }
+}
+
+/// 安裝 cgroup 與沙箱的 pre_exec 設定，沙箱無法啟用時結束程式。
+fn install_runner_hooks(runner: &mut Command, sandbox: bool) {
+    install_cgroup_hook(runner);
+    if sandbox && let Err(e) = apply_sandbox(runner) {
+        println!("❌ [SE] 無法啟用沙箱: {e}");
+        process::exit(1);
     }
 }
 
+/// 測資指定 `args` 時，複製 `runner` 的程式、參數、環境變數與工作目錄並附加這些參數。
+/// pre_exec 設定無法複製，因此重新安裝。
+fn runner_for_case(runner: &Command, case: &TestCase, sandbox: bool) -> Option<Command> {
+    if case.args.is_empty() {
+        return None;
+    }
+    let mut case_runner = Command::new(runner.get_program());
+    case_runner.args(runner.get_args()).args(&case.args);
+    for (key, value) in runner.get_envs() {
+        match value {
+            Some(value) => case_runner.env(key, value),
+            None => case_runner.env_remove(key),
+        };
+    }
+    if let Some(dir) = runner.get_current_dir() {
+        case_runner.current_dir(dir);
+    }
+    install_runner_hooks(&mut case_runner, sandbox);
+    Some(case_runner)
+}
+
 fn compile_source_code(info: &TestInfo, config: &EvaluatorConfig) -> Option<Command> {
+    if let Some(prebuilt) = &info.prebuilt {
+        // 使用絕對路徑，避免不含目錄的檔名被當成 PATH 中的指令
+        return match std::path::absolute(prebuilt) {
+            Ok(path) => Some(Command::new(path)),
+            Err(e) => {
+                println!("❌ [SE] {e}");
+                None
+            }
+        };
+    }
+
     let profile = config
         .languages
         .iter()
-        .find(|lang| lang.extension == info.file_type);
+        .find(|lang| lang.matches(&info.file_type));
     let Some(profile) = profile else {
         println!(
             "❌ [SE] 未知原始碼副檔名 {} ，請選擇 config.yaml 中含有的類型",
@@ -82,8 +251,25 @@ fn compile_source_code(info: &TestInfo, config: &EvaluatorConfig) -> Option<Comm
         println!("🔨 正在編譯檔案");
     }
 
-    match prepare_command(&info.file, profile) {
-        Ok(i) => Some(i),
+    let options = CompileOptions {
+        capture_output: info.capture_compile,
+        extra_args: info.compile_args.clone(),
+        program_overrides: info.program_overrides.clone(),
+    };
+
+    let compile_start = Instant::now();
+    let prepared = prepare_command(&info.file, profile, &options);
+    record(Phase::Compile, compile_start);
+    match prepared {
+        Ok(i) => {
+            if profile.compile.is_some() {
+                println!(
+                    "⏱️ 編譯耗時: {} ms",
+                    compile_start.elapsed().as_millis().prettify()
+                );
+            }
+            Some(i)
+        }
         Err(e) => {
             match e {
                 CompileError::SE(msg) => println!("❌ [SE] {msg}"),
@@ -94,7 +280,7 @@ fn compile_source_code(info: &TestInfo, config: &EvaluatorConfig) -> Option<Comm
     }
 }
 
-fn judge(info: TestInfo, mut runner: Command) {
+fn judge(info: TestInfo, mut runner: Command) -> SummaryInfo {
     let mut limit = Limitation::default();
 
     if let Some(time) = info.max_time {
@@ -105,9 +291,84 @@ fn judge(info: TestInfo, mut runner: Command) {
         limit.max_memory(Some(memory));
     }
 
+    limit.detect_empty_output(info.detect_empty_output);
+    limit.sandbox(info.sandbox);
+    limit.show_output(info.show_output);
+    limit.max_processes(info.max_processes);
+    limit.unicode_form(info.unicode_form);
+    limit.ignore_leading_whitespace(info.ignore_leading_whitespace);
+    limit.trim_trailing(info.trim_trailing);
+    limit.presentation_error(info.presentation_error.unwrap_or_default());
+    limit.compare_lines(info.compare_lines);
+    limit.max_mismatch(info.max_mismatch);
+    limit.compare_mode(info.compare_mode);
+    limit.exact_input(info.exact_input.unwrap_or_default());
+    limit.binary_io(info.binary_io);
+    if let Some(retries) = info.spawn_retries {
+        limit.spawn_retries(retries);
+    }
+    limit.memory_metric(info.memory_metric.unwrap_or_default());
+    let workdir = runner
+        .get_current_dir()
+        .map(Path::to_path_buf)
+        .or_else(|| env::current_dir().ok())
+        .unwrap_or_default();
+    limit.input_file(info.input_file.as_ref().map(|name| workdir.join(name)));
+    limit.output_file(info.output_file.as_ref().map(|name| workdir.join(name)));
+
+    let mut report_options = ReportOptions {
+        show_output: info.show_output,
+        explain_diff: info.explain_diff,
+        ..Default::default()
+    };
+    if let Some(max_diff_bytes) = info.max_diff_bytes {
+        report_options.max_diff_bytes = max_diff_bytes;
+    }
+
     let test_rounds: usize = info.cases.len();
     let mut summary_info = SummaryInfo::default();
-    let mut current_test_round: u32 = 0;
+
+    if let Some(seed) = info.shuffle_seed {
+        println!("🔀 以隨機順序執行測資 (seed: {seed})");
+    }
+    if let Some(count) = info.merged_cases {
+        println!("🧩 已將 {count} 筆測資合併為單一輸入 (T = {count})");
+    }
+
+    let previous_verdicts = if info.only_failing {
+        let previous = load_verdicts(&info.file);
+        if previous.is_none() {
+            println!("⚠️ 找不到上次的評測紀錄，將評測所有測資");
+        }
+        previous.unwrap_or_default()
+    } else {
+        BTreeMap::new()
+    };
+    let accepted: Vec<bool> = info
+        .cases
+        .iter()
+        .enumerate()
+        .map(|(index, case)| {
+            previous_verdicts
+                .get(&(index + 1))
+                .is_some_and(|previous| previous.accepted(case))
+        })
+        .collect();
+    let was_accepted = |index: usize| accepted[index];
+    let is_selected = |case: &TestCase| info.tags.is_empty() || case.has_any_tag(&info.tags);
+    if !info.tags.is_empty() {
+        let selected = info.cases.iter().filter(|case| is_selected(case)).count();
+        println!(
+            "🏷️ 只評測標籤為 {} 的測資 ({selected}/{test_rounds} 筆)",
+            info.tags.join("、")
+        );
+    }
+    let skipped = (0..test_rounds).filter(|&index| was_accepted(index)).count();
+    if skipped > 0 {
+        println!("⏭️ 略過 {skipped} 筆上次已通過的測資");
+    }
+    let mut verdicts = BTreeMap::new();
+    let mut flaky_rounds = 0;
 
     let mut report_table = Table::new();
     report_table.set_format(
@@ -122,32 +383,98 @@ fn judge(info: TestInfo, mut runner: Command) {
             .borders('|')
             .build(),
     );
-    report_table.set_titles(Row::new(vec![
+    let has_samples = info.cases.iter().any(|case| case.is_sample);
+    let mut titles = vec![
         Cell::new(""),
         Cell::new("測資"),
         Cell::new("用時 (ms)"),
         Cell::new("記憶體 (KiB)"),
         Cell::new("結果"),
-    ]));
+    ];
+    if has_samples {
+        titles.insert(2, Cell::new("類型"));
+    }
+    report_table.set_titles(Row::new(titles));
+    // 範例與隱藏測資各自的 (通過數, 總數)
+    let mut sample_tally = (0, 0);
+    let mut hidden_tally = (0, 0);
 
     if let Some(warmup) = info.warmup_times
         && warmup > 0
         && let Some(case) = info.cases.first()
     {
+        let mut case_runner = runner_for_case(&runner, case, info.sandbox);
+        let runner = case_runner.as_mut().unwrap_or(&mut runner);
         for _ in 0..warmup {
-            evaluate(&mut runner, &case.input, &case.answer, &limit);
+            timed(Phase::Judge, || {
+                evaluate(runner, case, &limit)
+            });
+            if is_interrupted() {
+                break;
+            }
         }
     }
 
-    for case in info.cases.iter() {
-        current_test_round += 1;
-        print_test_label(current_test_round);
+    let planned_rounds = (0..test_rounds)
+        .filter(|&index| !was_accepted(index) && is_selected(&info.cases[index]))
+        .count();
+    // 安靜模式下於終端機以同一行更新進度，結束前須先換行
+    let live_progress = info.quiet && io::stdout().is_terminal();
+
+    for index in case_order(test_rounds, info.shuffle_seed) {
+        let case = &info.cases[index];
+        if was_accepted(index) || !is_selected(case) {
+            continue;
+        }
+        // 暖身時被中斷則不再開始評測
+        if is_interrupted() {
+            println!("⚠️ 已中斷評測，以下為已完成測資的總結");
+            break;
+        }
+        let current_test_round = index + 1;
+        if !info.quiet {
+            print_test_label(current_test_round);
+        }
 
-        let verdict = evaluate(&mut runner, &case.input, &case.answer, &limit);
+        let mut case_runner = runner_for_case(&runner, case, info.sandbox);
+        let runner = case_runner.as_mut().unwrap_or(&mut runner);
+        let mut verdict = timed(Phase::Judge, || {
+            evaluate(runner, case, &limit)
+        });
+        // 重複執行時保留最嚴重的結果，並記錄各次結果的種類
+        let mut kinds = vec![verdict.status.kind()];
+        for _ in 1..info.repeat {
+            if is_interrupted() {
+                break;
+            }
+            let repeated = timed(Phase::Judge, || {
+                evaluate(runner, case, &limit)
+            });
+            kinds.push(repeated.status.kind());
+            if repeated.status.is_severe_than(&verdict.status) {
+                verdict = repeated;
+            }
+        }
+        if is_interrupted() {
+            if live_progress {
+                println!();
+            }
+            println!("⚠️ 已中斷評測，以下為已完成測資的總結");
+            break;
+        }
 
-        print_test_info(&verdict, &limit);
+        let is_flaky = kinds.iter().any(|&kind| kind != kinds[0]);
+        if is_flaky {
+            flaky_rounds += 1;
+        }
+        if !info.quiet {
+            print_test_info(&verdict, &limit, &report_options);
+            if is_flaky {
+                println!("🎲 {} 次執行結果不一致: {}", kinds.len(), tally(&kinds));
+            }
+        }
 
-        report_table.add_row(Row::new(vec![
+        let mut cells = vec![
             Cell::new(if verdict.is_accept() { "✅" } else { "❌" }),
             Cell::new(&current_test_round.to_string()),
             Cell::new(&verdict.duration.map_or_else(
@@ -159,10 +486,54 @@ fn judge(info: TestInfo, mut runner: Command) {
                     .memory
                     .map_or_else(|| "Unknown".to_owned(), |value| value.prettify()),
             ),
-            Cell::new(verdict.status.to_str_short()),
-        ]));
+            Cell::new(if is_flaky {
+                "不穩定 FLAKY"
+            } else {
+                verdict.status.to_str_short()
+            }),
+        ];
+        if has_samples {
+            cells.insert(2, Cell::new(if case.is_sample { "範例" } else { "隱藏" }));
+        }
+        // 安靜模式只列出未通過的測資
+        if !info.quiet || !verdict.is_accept() || is_flaky {
+            report_table.add_row(Row::new(cells));
+        }
+
+        let group = if case.is_sample {
+            &mut sample_tally
+        } else {
+            &mut hidden_tally
+        };
+        group.0 += usize::from(verdict.is_accept());
+        group.1 += 1;
+
+        verdicts.insert(
+            current_test_round,
+            CaseVerdict::new(verdict.status.kind(), case),
+        );
+        summary_info.update(current_test_round, verdict);
 
-        summary_info.update(verdict);
+        if live_progress {
+            print!(
+                "\r⏳ 已評測 {}/{planned_rounds}: 通過 {}，失敗 {}",
+                summary_info.current_rounds,
+                summary_info.success_rounds,
+                summary_info.current_rounds - summary_info.success_rounds
+            );
+            let _ = io::stdout().flush();
+        }
+    }
+    if live_progress && !is_interrupted() {
+        println!();
+    }
+
+    // 只評測部分測資時保留其餘測資上次的結果；合併後的單一測資不對應任何原本的測資，因此不記錄
+    let partial = info.only_failing || !info.tags.is_empty();
+    if info.merged_cases.is_none()
+        && let Err(e) = save_verdicts(&info.file, verdicts, partial)
+    {
+        log::warn!("無法儲存評測紀錄: {e}");
     }
 
     println!(
@@ -170,16 +541,167 @@ fn judge(info: TestInfo, mut runner: Command) {
         format!(
             "正確 {} 錯誤 {} 正確比 {}%",
             summary_info.success_rounds,
-            test_rounds - summary_info.success_rounds,
+            summary_info.current_rounds - summary_info.success_rounds,
             summary_info.score()
         )
     );
-    report_table.printstd();
+    if !report_table.is_empty() {
+        report_table.printstd();
+    }
 
     println!("🎯 {summary_info}");
+    if has_samples {
+        println!(
+            "📋 範例測資: 通過 {}/{}，隱藏測資: 通過 {}/{}",
+            sample_tally.0, sample_tally.1, hidden_tally.0, hidden_tally.1
+        );
+    }
+    if flaky_rounds > 0 {
+        println!("🎲 不穩定測資: {flaky_rounds} 筆");
+    }
+    if summary_info.total_mismatches > 0 {
+        println!("🧮 差異總數: {} 處", summary_info.total_mismatches);
+    }
+    if let Some((case_number, memory)) = summary_info.peak_memory {
+        println!(
+            "📈 記憶體峰值: 測資 {case_number} ({} KiB)",
+            memory.prettify()
+        );
+    }
+
+    if let Some(output) = &info.profile_output
+        && let Some(case) = info.cases.first()
+    {
+        println!("🔥 正在以第一筆測資進行效能分析");
+        let case_runner = runner_for_case(&runner, case, info.sandbox);
+        let runner = case_runner.as_ref().unwrap_or(&runner);
+        let input_file = info.input_file.as_ref().map(|name| workdir.join(name));
+        let input = prepare_input(case, &limit);
+        match run_profiler(runner, &input, input_file.as_deref(), Path::new(output)) {
+            Ok(artifact) => println!("🔥 分析結果已輸出至 {}", artifact.display()),
+            Err(e) => println!("❌ [SE] 效能分析失敗: {e}"),
+        }
+    }
+
+    summary_info
 }
 
-fn execute(mut runner: Command) {
+/// 印出 `--profile-phases` 的各階段耗時表，佔比以整體執行時間計算。
+fn print_phase_profile(run_start: Instant) {
+    let total = run_start.elapsed();
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(Row::new(vec![
+        Cell::new("階段"),
+        Cell::new("次數"),
+        Cell::new("耗時 (ms)"),
+        Cell::new("佔比"),
+    ]));
+    for (phase, elapsed, count) in phases::snapshot() {
+        table.add_row(Row::new(vec![
+            Cell::new(phase.label()),
+            Cell::new(&count.to_string()),
+            Cell::new(&elapsed.as_millis().prettify()),
+            Cell::new(&format!(
+                "{:.1}%",
+                elapsed.as_secs_f64() / total.as_secs_f64().max(f64::EPSILON) * 100.0
+            )),
+        ]));
+    }
+
+    println!("\n⏲️ 各階段耗時 (總計 {} ms):", total.as_millis().prettify());
+    table.printstd();
+}
+
+/// 依首次出現的順序統計各結果的次數，例如 `AC ×2, WA ×1`。
+fn tally(kinds: &[VerdictKind]) -> String {
+    let mut counts: Vec<(VerdictKind, usize)> = Vec::new();
+    for &kind in kinds {
+        match counts.iter_mut().find(|(counted, _)| *counted == kind) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((kind, 1)),
+        }
+    }
+    counts
+        .iter()
+        .map(|(kind, count)| format!("{kind} ×{count}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn check_inputs(info: &TestInfo) {
+    let Some(validator) = &info.validator else {
+        println!("❌ [SE] 未設定 validator，請在配置檔或 --validator 中指定驗證指令");
+        process::exit(1);
+    };
+
+    println!("🔍 正在驗證測資輸入格式");
+    match check_cases(validator, &info.cases) {
+        Ok(0) => println!("🎯 全部 {} 筆測資輸入格式正確", info.cases.len()),
+        Ok(invalid_count) => {
+            println!("🎯 {invalid_count} 筆測資輸入格式錯誤");
+            process::exit(1);
+        }
+        Err(e) => {
+            println!("❌ [SE] 無法執行驗證指令: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn execute(mut runner: Command, stdin_prompt: bool) {
+    if !stdin_prompt {
+        println!("⚙️ 正在運行程式");
+        let _ = runner.status();
+        return;
+    }
+
+    println!("⌨️ 請輸入測資 (Ctrl+D 結束輸入，Windows 為 Ctrl+Z 後按 Enter):");
+    let mut input = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut input) {
+        println!("❌ [SE] 無法讀取輸入: {e}");
+        return;
+    }
+    if is_interrupted() {
+        return;
+    }
+
     println!("⚙️ 正在運行程式");
-    let _ = runner.status();
+    let start_time = Instant::now();
+    let child = runner
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            println!("❌ [SE] 無法啟動程式: {e}");
+            return;
+        }
+    };
+    track_child(Some(child.id()));
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    let output = child.wait_with_output();
+    track_child(None);
+    let elapsed_time = start_time.elapsed();
+
+    match output {
+        Ok(output) => {
+            println!(
+                "{}\n{}",
+                center_text("Program Output", 30, "-"),
+                String::from_utf8_lossy(&output.stdout).trim_end()
+            );
+            if !output.status.success() {
+                println!("⚠️ 程式異常結束 ({})", output.status);
+            }
+            println!(
+                "⏱️ 程式執行耗時: {} ms",
+                elapsed_time.as_millis().prettify()
+            );
+        }
+        Err(e) => println!("❌ [SE] {e}"),
+    }
 }
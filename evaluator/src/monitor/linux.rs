@@ -1,27 +1,104 @@
 use cgroups_rs::Cgroup;
 use cgroups_rs::CgroupPid;
+use cgroups_rs::Controller;
+use cgroups_rs::MaxValue;
 use cgroups_rs::cgroup_builder::CgroupBuilder;
 use cgroups_rs::hierarchies;
 use cgroups_rs::memory::MemController;
+use cgroups_rs::pid::PidController;
 use rand::Rng;
+use std::fs::File;
+use std::io;
+use std::os::fd::{FromRawFd, IntoRawFd};
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::{self, Command};
+use std::sync::Once;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::thread;
 use std::time::Duration;
 
+use super::{MemoryMetric, MonitorReport};
+
 const CHECK_PROCESS_INTERVAL: Duration = Duration::from_millis(5);
 
-pub fn create_memory_monitor(pid: u32) -> Box<dyn FnOnce() -> Option<usize>> {
-    let cgroup_job = match CgroupJob::new(pid) {
-        Ok(job) => job,
-        Err(e) => {
-            log::warn!("無法創建 cgroup 來監控記憶體: {e}");
-            return Box::new(|| None);
-        }
+/// 下一個 spawn 的子程序要加入的 `cgroup.procs` 檔案描述符，-1 表示沒有。
+///
+/// cgroup v1 的 memory 與 pids 控制器位於不同階層，因此最多需要兩個。
+static STAGED_PROCS_FDS: [AtomicI32; 2] = [AtomicI32::new(-1), AtomicI32::new(-1)];
+
+/// 讓 `runner` 產生的子程序在 exec 之前就加入預先建立的 cgroup，
+/// 避免在加入 cgroup 前配置的記憶體未被計入。只需對每個 `Command` 呼叫一次。
+pub fn install_cgroup_hook(runner: &mut Command) {
+    // SAFETY: 閉包只讀取 atomic 並呼叫 write，不配置記憶體，可在 fork 後安全執行。
+    unsafe {
+        runner.pre_exec(|| {
+            for staged in &STAGED_PROCS_FDS {
+                let fd = staged.load(Ordering::SeqCst);
+                if fd >= 0 {
+                    // 寫入 0 代表將呼叫者自身加入 cgroup；失敗時由父程序在 spawn 後補加入
+                    libc::write(fd, b"0".as_ptr().cast(), 1);
+                }
+            }
+            Ok(())
+        })
     };
-    let monitor_thread = std::thread::spawn(move || monitor_cgroup_memory_usage(cgroup_job));
-    Box::new(|| monitor_thread.join().unwrap())
 }
 
-fn monitor_cgroup_memory_usage(job: CgroupJob) -> Option<usize> {
+/// 在 spawn 之前建立 cgroup，回傳的函式在 spawn 後以子程序的 PID 呼叫以開始監控。
+pub fn prepare_memory_monitor(
+    max_processes: Option<usize>,
+    metric: MemoryMetric,
+) -> impl FnOnce(u32) -> Box<dyn FnOnce() -> MonitorReport> {
+    if metric == MemoryMetric::Committed {
+        static WARN_COMMITTED: Once = Once::new();
+        WARN_COMMITTED
+            .call_once(|| log::warn!("Linux 不支援 committed 記憶體指標，將改用 working-set"));
+    }
+
+    let cgroup_job = CgroupJob::new(max_processes)
+        .inspect_err(|e| log::warn!("無法創建 cgroup 來監控記憶體: {e}"))
+        .ok();
+    if let Some(job) = &cgroup_job {
+        job.stage();
+    }
+    // spawn 失敗時回傳的函式不會被呼叫，由 guard 在被捨棄時關閉檔案描述符
+    let staged = StagedFdsGuard;
+
+    move |pid| {
+        drop(staged);
+        let Some(job) = cgroup_job else {
+            return Box::new(MonitorReport::default);
+        };
+        if let Err(e) = job.ensure_task(pid) {
+            log::warn!("無法將程序 {pid} 加入 cgroup: {e}");
+            return Box::new(MonitorReport::default);
+        }
+        let monitor_thread = std::thread::spawn(move || monitor_cgroup_memory_usage(job));
+        Box::new(|| monitor_thread.join().unwrap())
+    }
+}
+
+/// 被捨棄時關閉 [`CgroupJob::stage`] 開啟的檔案描述符，避免之後 spawn 的程序誤加入 cgroup。
+struct StagedFdsGuard;
+
+impl Drop for StagedFdsGuard {
+    fn drop(&mut self) {
+        unstage();
+    }
+}
+
+fn unstage() {
+    for staged in &STAGED_PROCS_FDS {
+        let fd = staged.swap(-1, Ordering::SeqCst);
+        if fd >= 0 {
+            // SAFETY: fd 由 `CgroupJob::stage` 以 `into_raw_fd` 取得，且只在此處關閉。
+            drop(unsafe { File::from_raw_fd(fd) });
+        }
+    }
+}
+
+fn monitor_cgroup_memory_usage(job: CgroupJob) -> MonitorReport {
     loop {
         let tasks = job.cgroup.tasks();
         if tasks.is_empty() {
@@ -30,28 +107,82 @@ fn monitor_cgroup_memory_usage(job: CgroupJob) -> Option<usize> {
         thread::sleep(CHECK_PROCESS_INTERVAL);
     }
     let mem_controller: &MemController = job.cgroup.controller_of().unwrap();
-    (mem_controller.memory_stat().max_usage_in_bytes / 1024)
+    let memory = (mem_controller.memory_stat().max_usage_in_bytes / 1024)
         .try_into()
-        .ok()
+        .ok();
+
+    // pids.events 記錄了因達到 pids.max 而失敗的 fork/clone 次數
+    let process_limit_exceeded = job.limit_processes
+        && job
+            .cgroup
+            .controller_of::<PidController>()
+            .and_then(|controller| controller.get_pid_events().ok())
+            .is_some_and(|events| events > 0);
+
+    MonitorReport {
+        memory,
+        process_limit_exceeded,
+    }
 }
 
 struct CgroupJob {
     cgroup: Cgroup,
+    limit_processes: bool,
 }
 
 impl CgroupJob {
-    pub fn new(pid: u32) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(max_processes: Option<usize>) -> Result<Self, Box<dyn std::error::Error>> {
         let random_suffix = rand::rng().random_range(10000..99999);
-        let cgroup_name = format!("offline-judge-{pid}-{random_suffix}");
+        let cgroup_name = format!("offline-judge-{}-{random_suffix}", process::id());
         let hier = hierarchies::auto();
-        let cgroup = CgroupBuilder::new(&cgroup_name)
-            .memory()
-            .done()
-            .build(hier)?;
-        cgroup
-            .add_task_by_tgid(CgroupPid::from(pid as u64))
-            .inspect_err(|e| log::warn!("{e}"))?;
-        Ok(Self { cgroup })
+        let mut builder = CgroupBuilder::new(&cgroup_name).memory().done();
+        if let Some(max_processes) = max_processes {
+            builder = builder
+                .pid()
+                .maximum_number_of_processes(MaxValue::Value(max_processes as i64))
+                .done();
+        }
+        let cgroup = builder.build(hier)?;
+        log::debug!("已建立 cgroup '{cgroup_name}'");
+        Ok(Self {
+            cgroup,
+            limit_processes: max_processes.is_some(),
+        })
+    }
+
+    /// 開啟各控制器的 `cgroup.procs`，供 [`install_cgroup_hook`] 在子程序中寫入。
+    fn stage(&self) {
+        let mut paths: Vec<&Path> = Vec::new();
+        if let Some(controller) = self.cgroup.controller_of::<MemController>() {
+            paths.push(controller.path());
+        }
+        if self.limit_processes
+            && let Some(controller) = self.cgroup.controller_of::<PidController>()
+            && !paths.contains(&controller.path())
+        {
+            paths.push(controller.path());
+        }
+
+        for (staged, path) in STAGED_PROCS_FDS.iter().zip(paths) {
+            match File::options().write(true).open(path.join("cgroup.procs")) {
+                Ok(file) => staged.store(file.into_raw_fd(), Ordering::SeqCst),
+                Err(e) => log::debug!("無法開啟 {}/cgroup.procs: {e}", path.display()),
+            }
+        }
+    }
+
+    /// 確認子程序已於 exec 前加入 cgroup，否則在此補加入。
+    fn ensure_task(&self, pid: u32) -> io::Result<()> {
+        let cgroup_pid = CgroupPid::from(pid as u64);
+        if self.cgroup.tasks().contains(&cgroup_pid) {
+            log::debug!("程序 {pid} 已於 exec 前加入 cgroup");
+            return Ok(());
+        }
+        self.cgroup
+            .add_task_by_tgid(cgroup_pid)
+            .map_err(io::Error::other)?;
+        log::debug!("已將程序 {pid} 加入 cgroup");
+        Ok(())
     }
 }
 
@@ -1,13 +1,60 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_os = "linux"))]
+use std::process::Command;
+
 #[cfg(target_os = "windows")]
 mod windows;
 #[cfg(target_os = "windows")]
-pub use windows::create_memory_monitor;
+use windows::create_memory_monitor;
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "linux")]
-pub use linux::create_memory_monitor;
+pub use linux::{install_cgroup_hook, prepare_memory_monitor};
 #[cfg(not(any(target_os = "linux", target_os = "windows")))]
-pub fn create_memory_monitor(_: u32) -> impl FnOnce() -> Option<usize> {
+fn create_memory_monitor(
+    _: u32,
+    _: Option<usize>,
+    _: MemoryMetric,
+) -> Box<dyn FnOnce() -> MonitorReport> {
     log::warn!("記憶體監控功能在此作業系統上不受支援。");
-    || None
+    Box::new(MonitorReport::default)
+}
+
+/// 只有 Linux 需要在 exec 前將子程序加入 cgroup，其他平台不需處理。
+#[cfg(not(target_os = "linux"))]
+pub fn install_cgroup_hook(_: &mut Command) {}
+
+/// 在 spawn 之前呼叫，回傳的函式在 spawn 後以子程序的 PID 呼叫以開始監控。
+#[cfg(not(target_os = "linux"))]
+pub fn prepare_memory_monitor(
+    max_processes: Option<usize>,
+    metric: MemoryMetric,
+) -> impl FnOnce(u32) -> Box<dyn FnOnce() -> MonitorReport> {
+    move |pid| create_memory_monitor(pid, max_processes, metric)
+}
+
+/// 回報的記憶體指標，單位一律為 KiB (1024 bytes)。
+///
+/// - Linux: 讀取 cgroup 記錄的整個程序群組用量峰值 (`memory.max_usage_in_bytes`)，
+///   由核心記錄而非取樣，包含匿名記憶體與頁面快取；不提供 `committed`。
+/// - Windows: 每 5 ms 取樣 Job 中各程序的 `PeakWorkingSetSize` (`working-set`)
+///   或 `PeakPagefileUsage` (`committed`) 並加總，於兩次取樣之間結束的子程序不會被計入。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MemoryMetric {
+    /// 實際常駐於實體記憶體的用量峰值。
+    #[default]
+    WorkingSet,
+    /// 已配置 (commit) 的私有記憶體峰值，不論是否常駐，僅 Windows 支援。
+    Committed,
+}
+
+/// 監控程序結束後回報的資源使用狀況。
+#[derive(Debug, Default)]
+pub struct MonitorReport {
+    /// 記憶體使用量峰值 (KiB)。
+    pub memory: Option<usize>,
+    /// 程序是否曾因超過 `max_processes` 而無法建立新的程序或執行緒。
+    pub process_limit_exceeded: bool,
 }
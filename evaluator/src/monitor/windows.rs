@@ -1,37 +1,111 @@
+use std::ffi::c_void;
+use std::mem::size_of;
+use std::ptr;
 use std::thread;
 use std::time::Duration;
 use win32job::{ExtendedLimitInfo, Job};
-use windows::Win32::Foundation::{CloseHandle, HANDLE, STILL_ACTIVE};
+use windows::Win32::Foundation::{CloseHandle, HANDLE, INVALID_HANDLE_VALUE, STILL_ACTIVE};
+use windows::Win32::System::IO::{CreateIoCompletionPort, GetQueuedCompletionStatus, OVERLAPPED};
+use windows::Win32::System::JobObjects::{
+    JOB_OBJECT_LIMIT_ACTIVE_PROCESS, JOBOBJECT_ASSOCIATE_COMPLETION_PORT,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JobObjectAssociateCompletionPortInformation,
+    JobObjectExtendedLimitInformation, QueryInformationJobObject, SetInformationJobObject,
+};
 use windows::Win32::System::ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+use windows::Win32::System::SystemServices::JOB_OBJECT_MSG_ACTIVE_PROCESS_LIMIT;
 use windows::Win32::System::Threading::{
     GetExitCodeProcess, OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_SET_QUOTA,
     PROCESS_TERMINATE, PROCESS_VM_READ,
 };
 
+use super::{MemoryMetric, MonitorReport};
+
 const CHECK_MEMORY_INTERVAL: Duration = Duration::from_millis(5);
 
-pub fn create_memory_monitor(pid: u32) -> Box<dyn FnOnce() -> Option<usize>> {
-    let job = match apply_job_for_process(pid) {
-        Ok(job) => job,
+pub fn create_memory_monitor(
+    pid: u32,
+    max_processes: Option<usize>,
+    metric: MemoryMetric,
+) -> Box<dyn FnOnce() -> MonitorReport> {
+    let (job, limit_port) = match apply_job_for_process(pid, max_processes) {
+        Ok(applied) => applied,
         Err(e) => {
             log::warn!("無法取得記憶體使用量: {e}");
-            return Box::new(|| None);
+            return Box::new(MonitorReport::default);
         }
     };
 
-    let monitor_thread = std::thread::spawn(move || monitor_job_memory_usage(job));
-    Box::new(|| monitor_thread.join().unwrap())
+    let monitor_thread = std::thread::spawn(move || monitor_job_memory_usage(job, metric));
+    Box::new(|| MonitorReport {
+        memory: monitor_thread.join().unwrap(),
+        process_limit_exceeded: limit_port.is_some_and(|port| port.limit_exceeded()),
+    })
 }
 
-fn apply_job_for_process(pid: u32) -> Result<Job, Box<dyn std::error::Error>> {
+fn apply_job_for_process(
+    pid: u32,
+    max_processes: Option<usize>,
+) -> Result<(Job, Option<CompletionPort>), Box<dyn std::error::Error>> {
     let handle = pid_to_handle(pid)?;
     let job = Job::create_with_limit_info(ExtendedLimitInfo::new().limit_kill_on_job_close())?;
+    let limit_port = match max_processes {
+        Some(max_processes) => Some(limit_active_processes(&job, max_processes)?),
+        None => None,
+    };
 
     job.assign_process(handle.0)?;
-    Ok(job)
+    log::debug!("已將程序 {pid} 加入 Job 物件");
+    Ok((job, limit_port))
+}
+
+/// 設定 Job 的 `ActiveProcessLimit`，並關聯一個完成埠以得知是否曾因此無法建立程序。
+///
+/// Job 物件只限制程序數量，執行緒不計入。
+fn limit_active_processes(
+    job: &Job,
+    max_processes: usize,
+) -> Result<CompletionPort, windows::core::Error> {
+    let job_handle = HANDLE(job.handle());
+    let mut info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+    let info_size = size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32;
+    unsafe {
+        QueryInformationJobObject(
+            job_handle,
+            JobObjectExtendedLimitInformation,
+            ptr::addr_of_mut!(info).cast::<c_void>(),
+            info_size,
+            None,
+        )?;
+        info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_ACTIVE_PROCESS;
+        info.BasicLimitInformation.ActiveProcessLimit =
+            max_processes.try_into().unwrap_or(u32::MAX);
+        SetInformationJobObject(
+            job_handle,
+            JobObjectExtendedLimitInformation,
+            ptr::addr_of!(info).cast::<c_void>(),
+            info_size,
+        )?;
+    }
+
+    let port = CompletionPort {
+        handle: unsafe { CreateIoCompletionPort(INVALID_HANDLE_VALUE, HANDLE::default(), 0, 1)? },
+    };
+    let association = JOBOBJECT_ASSOCIATE_COMPLETION_PORT {
+        CompletionKey: ptr::null_mut(),
+        CompletionPort: port.handle,
+    };
+    unsafe {
+        SetInformationJobObject(
+            job_handle,
+            JobObjectAssociateCompletionPortInformation,
+            ptr::addr_of!(association).cast::<c_void>(),
+            size_of::<JOBOBJECT_ASSOCIATE_COMPLETION_PORT>() as u32,
+        )?;
+    }
+    Ok(port)
 }
 
-fn monitor_job_memory_usage(job: Job) -> Option<usize> {
+fn monitor_job_memory_usage(job: Job, metric: MemoryMetric) -> Option<usize> {
     let mut max_memory_usage = 0;
     loop {
         let pids = match job.query_process_id_list() {
@@ -57,7 +131,7 @@ fn monitor_job_memory_usage(job: Job) -> Option<usize> {
                         return 0;
                     }
                 };
-                get_memory_usage(&handle).unwrap_or(0)
+                get_memory_usage(&handle, metric).unwrap_or(0)
             })
             .sum();
 
@@ -80,7 +154,7 @@ fn pid_to_handle(pid: u32) -> Result<HANDLE, windows::core::Error> {
     }
 }
 
-fn get_memory_usage(handle: &ProcessHandle) -> Option<usize> {
+fn get_memory_usage(handle: &ProcessHandle, metric: MemoryMetric) -> Option<usize> {
     if !handle.is_alive() {
         return None;
     }
@@ -91,7 +165,10 @@ fn get_memory_usage(handle: &ProcessHandle) -> Option<usize> {
     let cb = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
 
     if unsafe { GetProcessMemoryInfo(process_handle, &mut pmc, cb) }.is_ok() {
-        let memory_usage_bytes = pmc.PeakWorkingSetSize;
+        let memory_usage_bytes = match metric {
+            MemoryMetric::WorkingSet => pmc.PeakWorkingSetSize,
+            MemoryMetric::Committed => pmc.PeakPagefileUsage,
+        };
         let memory_usage_kb = memory_usage_bytes / 1024;
         Some(memory_usage_kb)
     } else {
@@ -136,6 +213,37 @@ impl ProcessHandle {
     }
 }
 
+/// 接收 Job 通知的 I/O 完成埠。
+struct CompletionPort {
+    handle: HANDLE,
+}
+
+impl CompletionPort {
+    /// 取出所有已送達的通知，回傳 Job 是否曾因超過 `ActiveProcessLimit` 而無法建立程序。
+    fn limit_exceeded(&self) -> bool {
+        let mut exceeded = false;
+        let mut message: u32 = 0;
+        let mut key: usize = 0;
+        let mut overlapped: *mut OVERLAPPED = ptr::null_mut();
+        while unsafe {
+            GetQueuedCompletionStatus(self.handle, &mut message, &mut key, &mut overlapped, 0)
+        }
+        .is_ok()
+        {
+            exceeded |= message == JOB_OBJECT_MSG_ACTIVE_PROCESS_LIMIT;
+        }
+        exceeded
+    }
+}
+
+impl Drop for CompletionPort {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseHandle(self.handle);
+        }
+    }
+}
+
 impl Drop for ProcessHandle {
     fn drop(&mut self) {
         unsafe {
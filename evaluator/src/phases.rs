@@ -0,0 +1,61 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// `--profile-phases` 統計的執行階段。
+#[derive(Clone, Copy)]
+pub enum Phase {
+    ReadConfig,
+    Compile,
+    Judge,
+    Monitor,
+}
+
+impl Phase {
+    pub const ALL: [Self; 4] = [Self::ReadConfig, Self::Compile, Self::Judge, Self::Monitor];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ReadConfig => "讀取設定",
+            Self::Compile => "編譯",
+            Self::Judge => "評測",
+            Self::Monitor => "監控 (含於評測)",
+        }
+    }
+}
+
+/// 各階段累計的 (耗時, 次數)，以 `Phase` 的順序排列。
+static PHASES: Mutex<[(Duration, u32); Phase::ALL.len()]> =
+    Mutex::new([(Duration::ZERO, 0); Phase::ALL.len()]);
+
+/// 將 `start` 至今的時間累加到 `phase`。
+pub fn record(phase: Phase, start: Instant) {
+    add(phase, start.elapsed());
+}
+
+/// 將 `elapsed` 累加到 `phase`，並計為執行一次。
+pub fn add(phase: Phase, elapsed: Duration) {
+    if let Ok(mut phases) = PHASES.lock() {
+        let (total, count) = &mut phases[phase as usize];
+        *total += elapsed;
+        *count += 1;
+    }
+}
+
+/// 在 `phase` 中執行 `f` 並記錄其耗時。
+pub fn timed<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    record(phase, start);
+    result
+}
+
+/// 回傳各階段的累計耗時與次數，未執行過的階段會被略過。
+pub fn snapshot() -> Vec<(Phase, Duration, u32)> {
+    let phases = PHASES.lock().map(|phases| *phases).unwrap_or_default();
+    Phase::ALL
+        .into_iter()
+        .zip(phases)
+        .filter(|(_, (_, count))| *count > 0)
+        .map(|(phase, (total, count))| (phase, total, count))
+        .collect()
+}
@@ -0,0 +1,91 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// 以外部效能分析工具包裝 `runner`，並餵入 `input` 執行一次。
+///
+/// 指定 `input_file` 時將輸入寫入該檔案而非 stdin，與評測時相同；
+/// 工作目錄沿用 `runner` 的設定。
+///
+/// Linux 透過 `flamegraph` (內部呼叫 `perf record -g`) 輸出 SVG；
+/// macOS 透過 `xcrun xctrace` 輸出 `.trace`。分析工具需位於 PATH 中。
+///
+/// # Returns
+/// * `Ok(PathBuf)` - 產出檔案的路徑。
+/// * `Err(io::Error)` - 分析工具無法啟動或執行失敗。
+pub fn run_profiler(
+    runner: &Command,
+    input: &[u8],
+    input_file: Option<&Path>,
+    output: &Path,
+) -> io::Result<PathBuf> {
+    let output = std::path::absolute(output)?;
+    let (mut cmd, artifact) = profiler_command(&output)?;
+
+    if let Some(path) = input_file {
+        fs::write(path, input)?;
+    }
+    cmd.arg(runner.get_program()).args(runner.get_args());
+    if let Some(dir) = runner.get_current_dir() {
+        cmd.current_dir(dir);
+    }
+    let mut child = cmd
+        .stdin(if input_file.is_some() {
+            Stdio::null()
+        } else {
+            Stdio::piped()
+        })
+        .stdout(Stdio::null())
+        .spawn()?;
+
+    // 程式可能不讀完輸入就結束，此時仍以分析工具的結束狀態為準
+    let write_result = match child.stdin.take() {
+        Some(mut stdin) => stdin.write_all(input),
+        None => Ok(()),
+    };
+    let status = child.wait()?;
+    if let Err(e) = write_result
+        && e.kind() != io::ErrorKind::BrokenPipe
+    {
+        return Err(e);
+    }
+    if !status.success() {
+        return Err(io::Error::other(format!("分析工具結束狀態異常: {status}")));
+    }
+
+    Ok(artifact)
+}
+
+#[cfg(target_os = "linux")]
+fn profiler_command(output: &Path) -> io::Result<(Command, PathBuf)> {
+    let mut cmd = Command::new("flamegraph");
+    cmd.args(["--cmd", "record -g", "--output"])
+        .arg(output)
+        .arg("--");
+    Ok((cmd, output.to_path_buf()))
+}
+
+#[cfg(target_os = "macos")]
+fn profiler_command(output: &Path) -> io::Result<(Command, PathBuf)> {
+    let trace = output.with_extension("trace");
+    let mut cmd = Command::new("xcrun");
+    cmd.args([
+        "xctrace",
+        "record",
+        "--template",
+        "Time Profiler",
+        "--output",
+    ])
+    .arg(&trace)
+    .args(["--launch", "--"]);
+    Ok((cmd, trace))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn profiler_command(_output: &Path) -> io::Result<(Command, PathBuf)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "效能分析功能在此作業系統上不受支援。",
+    ))
+}
@@ -1,43 +1,337 @@
 use super::error::ReaderError;
-use super::test_cases::{TestCase, TestCasePath, read_test_cases};
+use super::rc::{ProjectRc, find_project_rc};
+use super::test_cases::{ConfigFormat, TestCase, TestCasePath, TestCases, read_test_cases};
 use super::utils::{change_extension, file_exists};
+use crate::judge::verdict::VerdictKind;
+use crate::judge::{CompareMode, UnicodeForm};
 use crate::logger::init_logger;
+use crate::monitor::MemoryMetric;
 use crate::reader::EvaluatorConfig;
-use clap::Parser;
+use crate::utils::random_seed;
+use clap::{Parser, ValueEnum};
 use std::{path::Path, time::Duration};
 
 /// Evaluator - Code Judge Tool
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct Args {
+    /// 直接評測已編譯好的執行檔 (與原始碼分開時使用)，隱含 `--no-compile`。
+    /// Judge a pre-built executable kept apart from the source; implies `--no-compile`.
+    #[arg(long, value_name = "PATH")]
+    pub binary: Option<String>,
+
+    /// 評測結束時發出終端機提示音，可指定僅在失敗時發出 (輸出非終端機時不發出)。
+    /// Ring the terminal bell when judging finishes, optionally only on failure (skipped when not a TTY).
+    #[arg(
+        long,
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "always"
+    )]
+    pub bell: Option<BellMode>,
+
+    /// 僅以驗證程式檢查每筆測資的輸入格式，不編譯也不評測。
+    /// Only check each case's input with the validator, without compiling or judging.
+    #[arg(long, conflicts_with = "no_judge")]
+    pub check: bool,
+
+    /// 僅編譯原始碼並以編譯結果作為狀態碼結束，不執行也不評測，此模式下不需要設定檔。
+    /// Only compile the source and exit with the compile status, without running or judging.
+    #[arg(long("compile-only"), conflicts_with_all = ["check", "no_judge"])]
+    pub compile_only: bool,
+
+    /// 附加於編譯指令的額外參數 (例如 "-DLOCAL -g")，接在設定檔的 compile_args 之後。
+    /// Extra compiler flags (e.g. "-DLOCAL -g"), appended after the profile's compile_args.
+    #[arg(long("compile-args"), value_name = "ARGS", allow_hyphen_values = true)]
+    pub compile_args: Option<String>,
+
+    /// 只比對輸出與答案的前 N 行，忽略其後的內容 (覆蓋配置檔中的 compare_lines)。
+    /// Only compare the first N lines of output against the answer (overrides `compare_lines` in the config file).
+    #[arg(long("compare-lines"), value_name = "N")]
+    pub compare_lines: Option<usize>,
+
+    /// 輸出與答案不同之處不超過 N 時仍判為 AC (覆蓋配置檔中的 max_mismatch)。
+    /// Accept outputs with at most N mismatched lines or tokens (overrides `max_mismatch` in the config file).
+    #[arg(long("max-mismatch"), value_name = "N")]
+    pub max_mismatch: Option<usize>,
+
+    /// 判定方式 (覆蓋配置檔中的 compare_mode)：exact 須與答案相同；
+    /// subsequence 只要答案的每一行依序出現在輸出中即為正確，可穿插其他輸出。
+    /// How output is judged (overrides `compare_mode` in the config file): `exact` must match the answer;
+    /// `subsequence` accepts output containing the answer's lines in order, with other lines in between.
+    #[arg(long("compare-mode"), value_enum, value_name = "MODE")]
+    pub compare_mode: Option<CompareMode>,
+
     /// 設定檔的路徑 (可選)。
     /// Path to the configuration file (optional).
     /// 若未提供，程式預設會尋找與輸入檔案同名的 .yaml 檔。
     /// If not provided, it default to a .yaml file with the same name as the input file.
+    /// 依序尋找 .yaml、.yml 與 .toml。
+    /// Searched in the order .yaml, .yml, .toml.
     #[arg(short, long)]
     pub config: Option<String>,
 
-    /// 要執行或測試的檔案路徑。
-    /// The file path to execute or test.
-    #[arg(index(1))]
-    pub file: String,
+    /// 強制以指定格式讀取配置檔，而非依副檔名判斷。
+    /// Force the configuration file format instead of inferring it from the extension.
+    #[arg(long("config-format"), value_enum, value_name = "FORMAT")]
+    pub config_format: Option<ConfigFormat>,
+
+    /// 傳遞給受測程式的額外命令列參數，須置於最後 (例如 `--args --debug 1`)。
+    /// Extra arguments passed to the solution program; must come last (e.g. `--args --debug 1`).
+    #[arg(long("args"), num_args = 1.., allow_hyphen_values = true)]
+    pub extra_args: Vec<String>,
+
+    /// 緩衝編譯器輸出，僅在編譯失敗時顯示 (預設為即時輸出)。
+    /// Buffer compiler output and only show it on failure (streams live by default).
+    #[arg(long("capture-compile"))]
+    pub capture_compile: bool,
+
+    /// 關閉專案設定檔 (.ojrc.yaml) 中開啟的 capture-compile。
+    /// Turn off `capture-compile` enabled by the project rc file (.ojrc.yaml).
+    #[arg(long("no-capture-compile"), overrides_with = "capture_compile")]
+    pub no_capture_compile: bool,
+
+    /// 驗證測資輸入格式的指令，覆蓋設定檔中的 validator。
+    /// Command that validates case inputs; overrides `validator` in the config file.
+    #[arg(long, value_name = "COMMAND")]
+    pub validator: Option<String>,
+
+    /// 預期的整體評測結果，僅在實際結果相符時以狀態碼 0 結束 (用於驗證測資與限制設定)。
+    /// Expected overall verdict; exit with status 0 only if the actual verdict matches.
+    #[arg(long, value_enum, ignore_case = true)]
+    pub expect: Option<VerdictKind>,
+
+    /// 要執行或測試的檔案路徑，可傳入多個檔案或萬用字元樣式 (例如 `sol_*.cpp`) 逐一評測。
+    /// The file paths to execute or test; several files or glob patterns (e.g. `sol_*.cpp`) are judged in turn.
+    #[arg(index(1), num_args = 1.., required = true)]
+    pub files: Vec<String>,
+
+    /// 將測資輸入寫入工作目錄中的指定檔案，而非由 stdin 餵入 (覆蓋配置檔中的 input_file)。
+    /// Write each case's input to this file in the working directory instead of stdin.
+    #[arg(long("input-file"), value_name = "NAME")]
+    pub input_file: Option<String>,
+
+    /// 從指定檔案讀取單筆測資的輸入，須搭配 --answer-file，並略過配置檔。
+    /// Read a single case's input from this file (requires --answer-file; skips the config file).
+    #[arg(
+        long("stdin-file"),
+        value_name = "PATH",
+        requires = "answer_file",
+        conflicts_with_all = ["config", "no_judge"]
+    )]
+    pub stdin_file: Option<String>,
+
+    /// 搭配 --stdin-file 使用的答案檔。
+    /// Expected output for --stdin-file.
+    #[arg(long("answer-file"), value_name = "PATH", requires = "stdin_file")]
+    pub answer_file: Option<String>,
+
+    /// 評測多個檔案時，某個檔案編譯失敗或結果不符預期仍繼續評測其餘檔案。
+    /// When judging several files, keep going after a file fails to compile or misses `--expect`.
+    #[arg(long("keep-going"))]
+    pub keep_going: bool,
+
+    /// 關閉專案設定檔 (.ojrc.yaml) 中開啟的 keep-going。
+    /// Turn off `keep-going` enabled by the project rc file (.ojrc.yaml).
+    #[arg(long("no-keep-going"), overrides_with = "keep_going")]
+    pub no_keep_going: bool,
 
     /// 指定檔案的程式語言 (可選)。
     /// The programming language for compiling or running (optional).
     #[arg(short, long)]
     pub lang: Option<String>,
 
+    /// 替換編譯或執行指令開頭的程式，格式為 鍵=路徑，可重複指定。鍵為語言副檔名時替換該語言的編譯器
+    /// (直譯語言為直譯器)，也可直接使用指令中的程式名稱，例如 `--lang-bin cpp=/usr/bin/g++-13`、`--lang-bin python=/opt/py311/bin/python`。
+    /// Replace the program that starts a compile or run command, as KEY=PATH; repeatable. A language extension
+    /// replaces that language's compiler (or interpreter), and a program name as written in the command
+    /// replaces it wherever it appears, e.g. `--lang-bin cpp=/usr/bin/g++-13`.
+    #[arg(long("lang-bin"), value_name = "KEY=PATH", value_parser = parse_lang_bin)]
+    pub lang_bins: Vec<(String, String)>,
+
+    /// 將記錄寫入指定檔案，不影響終端機輸出。
+    /// Write logs to the given file, keeping the console clean.
+    #[arg(long("log-file"), value_name = "PATH")]
+    pub log_file: Option<String>,
+
+    /// 記錄檔的記錄等級 (預設 debug)。
+    /// Log level for the log file (default: debug).
+    #[arg(long("log-level"), default_value = "debug", requires = "log_file")]
+    pub log_level: log::LevelFilter,
+
+    /// WA 時差異區塊最多輸出的位元組數 (預設 65536)。
+    /// Maximum bytes printed for the WA diff section (default: 65536).
+    #[arg(long("max-diff-bytes"), value_name = "BYTES")]
+    pub max_diff_bytes: Option<usize>,
+
     /// 設定單一測試案例的最大記憶體用量限制 (單位: KiB)。
     /// Maximum memory usage (in KiB) for a single test case.
     #[arg(short('M'), long)]
     pub memory: Option<usize>,
 
+    /// 回報的記憶體指標：working-set (常駐記憶體峰值) 或 committed (已配置記憶體峰值，僅 Windows)。
+    /// Memory metric to report: working-set (peak resident) or committed (peak private commit, Windows only).
+    #[arg(long("memory-metric"), value_enum, value_name = "METRIC")]
+    pub memory_metric: Option<MemoryMetric>,
+
+    /// 數字不加千分位分隔符，方便外部工具解析輸出。
+    /// Print numbers without thousands separators, easing parsing by external tools.
+    #[arg(long("no-group"))]
+    pub no_group: bool,
+
+    /// 略過編譯，將檔案路徑直接視為執行檔 (例如以 Makefile 自行編譯時)。
+    /// Skip compilation and treat the file path as the executable (e.g. when built with a Makefile).
+    #[arg(short('C'), long("no-compile"), conflicts_with = "compile_only")]
+    pub no_compile: bool,
+
     /// 啟用「無評判模式」，此模式下不需要設定檔。
     /// Enable "No Judgement Mode", which does not require a config file.
     /// CLI: -n, --no-judge
     #[arg(short, long("no-judge"))]
     pub no_judge: bool,
 
+    /// 只評測上次評測結果不是 AC 的測資，上次已通過的測資會被略過。
+    /// Only judge the cases that were not AC in the previous run of this file.
+    #[arg(long("only-failing"), conflicts_with_all = ["compile_only", "no_judge"])]
+    pub only_failing: bool,
+
+    /// 只評測帶有指定標籤的測資，可重複指定 (符合任一即可)；範例測資自帶 sample 標籤。
+    /// Only judge cases carrying this tag; repeatable (any match). Sample cases implicitly carry `sample`.
+    #[arg(long("tag"), value_name = "NAME", conflicts_with_all = ["compile_only", "no_judge"])]
+    pub tags: Vec<String>,
+
+    /// 從工作目錄中的指定檔案讀取程式輸出，而非 stdout (覆蓋配置檔中的 output_file)。
+    /// Read the program's output from this file in the working directory instead of stdout.
+    #[arg(long("output-file"), value_name = "NAME")]
+    pub output_file: Option<String>,
+
+    /// 輸出僅空白或換行與答案不同時判為格式錯誤 (PE) 而非 WA。
+    /// Report Presentation Error instead of WA when only whitespace or line breaks differ.
+    #[arg(long("presentation-error"))]
+    pub presentation_error: bool,
+
+    /// 關閉專案設定檔 (.ojrc.yaml) 中開啟的 presentation-error。
+    /// Turn off `presentation-error` enabled by the project rc file (.ojrc.yaml).
+    #[arg(long("no-presentation-error"), overrides_with = "presentation_error")]
+    pub no_presentation_error: bool,
+
+    /// 原樣寫入測資輸入 (覆蓋配置檔中的 exact_input)；預設會在輸入缺少結尾換行時補上一個。
+    /// Feed each case's input exactly as written (overrides `exact_input` in the config file);
+    /// by default a trailing newline is appended when the input lacks one.
+    #[arg(long("no-trim"))]
+    pub no_trim: bool,
+
+    /// 以位元組處理輸入輸出 (覆蓋配置檔中的 binary_io)：原樣寫入輸入，並逐位元組比對輸出與答案。
+    /// 搭配測資的 input_path/answer_path 從檔案讀取非文字資料。
+    /// Treat I/O as raw bytes (overrides `binary_io` in the config file): input is fed verbatim and output is
+    /// compared byte for byte. Use `input_path`/`answer_path` in cases to load non-text data from files.
+    #[arg(long("binary-io"))]
+    pub binary_io: bool,
+
+    /// 將所有測資合併為一次執行 (覆蓋配置檔中的 multi_test)：輸入第一行為測資數 T，其後依序接上各筆輸入，
+    /// 輸出須等於各筆答案依序串接。
+    /// Run all cases as one input (overrides `multi_test` in the config file): the first line is the case
+    /// count T followed by each case's input, and the output must equal the answers concatenated in order.
+    #[arg(long("multi-test"))]
+    pub multi_test: bool,
+
+    /// 以第一筆測資進行效能分析，並將火焰圖輸出至指定路徑 (需安裝 flamegraph 或 xctrace)。
+    /// Profile the first test case and write a flamegraph to the given path (requires flamegraph or xctrace).
+    #[arg(long, value_name = "OUTPUT")]
+    pub profile: Option<String>,
+
+    /// 結束時列出讀取設定、編譯、評測與監控各階段的耗時。
+    /// Print a wall-clock breakdown of config reading, compilation, judging and monitoring at the end of the run.
+    #[arg(long("profile-phases"))]
+    pub profile_phases: bool,
+
+    /// 安靜模式：不逐筆顯示測資資訊，只在終端機上以單行即時更新通過與失敗數，結束時列出未通過的測資與總結。
+    /// Quiet mode: skip per-case details and keep a single live pass/fail line on a TTY, then list the failing
+    /// cases and the summary.
+    #[arg(short, long, conflicts_with = "no_judge")]
+    pub quiet: bool,
+
+    /// 每筆測資重複執行 N 次，各次結果不一致時標記為不穩定 (FLAKY) 並視為錯誤。
+    /// Run each case N times; cases whose verdicts differ between runs are flagged as FLAKY and count as failures.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub repeat: Option<u32>,
+
+    /// 以 seccomp 沙箱執行受測程式，禁止網路與建立子程序等系統呼叫 (僅支援 Linux)。
+    /// Run the solution in a seccomp sandbox that denies network and process creation (Linux only).
+    #[arg(long)]
+    pub sandbox: bool,
+
+    /// 關閉專案設定檔 (.ojrc.yaml) 中開啟的 sandbox。
+    /// Turn off `sandbox` enabled by the project rc file (.ojrc.yaml).
+    #[arg(long("no-sandbox"), overrides_with = "sandbox")]
+    pub no_sandbox: bool,
+
+    /// 答案正確時也顯示程式輸出，方便檢查輸出格式。
+    /// Also print the program output on AC, to eyeball formatting.
+    #[arg(long("show-output"))]
+    pub show_output: bool,
+
+    /// 關閉專案設定檔 (.ojrc.yaml) 中開啟的 show-output。
+    /// Turn off `show-output` enabled by the project rc file (.ojrc.yaml).
+    #[arg(long("no-show-output"), overrides_with = "show_output")]
+    pub no_show_output: bool,
+
+    /// 答案錯誤時合併顯示輸出與答案的逐字差異 (類似 git diff --word-diff)，取代分開的兩個區塊。
+    /// On WA, show one merged character-level diff (like `git diff --word-diff`) instead of separate
+    /// output and answer blocks.
+    #[arg(long("explain-diff"))]
+    pub explain_diff: bool,
+
+    /// 關閉專案設定檔 (.ojrc.yaml) 中開啟的 explain-diff。
+    /// Turn off `explain-diff` enabled by the project rc file (.ojrc.yaml).
+    #[arg(long("no-explain-diff"), overrides_with = "explain_diff")]
+    pub no_explain_diff: bool,
+
+    /// 以隨機順序執行測資，用於檢查程式是否依賴測資順序。
+    /// Run test cases in a random order to catch order-dependent solutions.
+    #[arg(long("shuffle-cases"))]
+    pub shuffle_cases: bool,
+
+    /// 打亂測資時使用的隨機種子，用於重現執行順序。
+    /// Seed for `--shuffle-cases`, making the order reproducible.
+    #[arg(long, requires = "shuffle_cases")]
+    pub seed: Option<u64>,
+
+    /// 搭配 --no-judge 使用：先從終端機讀取輸入直到 EOF，再執行程式並顯示輸出與耗時。
+    /// With --no-judge, read input from the terminal until EOF, then run the program and show its output and timing.
+    #[arg(long("stdin-prompt"), requires = "no_judge")]
+    pub stdin_prompt: bool,
+
+    /// 比對前將輸出與答案轉為指定的 Unicode 正規化形式 (NFC、NFD、NFKC、NFKD)。
+    /// Normalize output and answers to the given Unicode form before comparing.
+    #[arg(
+        long("unicode-normalize"),
+        value_enum,
+        ignore_case = true,
+        value_name = "FORM"
+    )]
+    pub unicode_normalize: Option<UnicodeForm>,
+
+    /// 比對前移除輸出與答案每一行開頭的空白，例如 `    5` 視為與 `5` 相同。
+    /// Ignore leading whitespace on every line of output and answers, so `    5` matches `5`.
+    #[arg(long("ignore-leading-whitespace"))]
+    pub ignore_leading_whitespace: bool,
+
+    /// 關閉專案設定檔 (.ojrc.yaml) 中開啟的 ignore-leading-whitespace。
+    /// Turn off `ignore-leading-whitespace` enabled by the project rc file (.ojrc.yaml).
+    #[arg(
+        long("no-ignore-leading-whitespace"),
+        overrides_with = "ignore_leading_whitespace"
+    )]
+    pub no_ignore_leading_whitespace: bool,
+
+    /// 啟動受測程式因系統資源暫時不足 (EAGAIN) 而失敗時的重試次數，每次間隔加倍 (預設: 3)。
+    /// Retries when starting the program fails with a transient resource error (EAGAIN), with
+    /// doubling backoff (default: 3).
+    #[arg(long("spawn-retries"), value_name = "N")]
+    pub spawn_retries: Option<u32>,
+
     /// 設定單一測試案例的最大執行時間限制 (單位: 毫秒 ms)。
     /// Maximum time (in milliseconds) for a single test case.
     #[arg(short('T'), long)]
@@ -56,45 +350,211 @@ pub struct Args {
     pub warmup: Option<u32>,
 }
 
-pub fn resolve_args() -> Result<TestInfo, ReaderError> {
+/// `--bell` 發出提示音的時機。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BellMode {
+    /// 評測結束時一律發出。
+    Always,
+    /// 僅在有測資未通過或編譯失敗時發出。
+    Failure,
+}
+
+fn parse_lang_bin(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, path)) if !key.is_empty() && !path.is_empty() => {
+            Ok((key.to_owned(), path.to_owned()))
+        }
+        _ => Err(format!(
+            "格式應為 鍵=路徑，例如 cpp=/usr/bin/g++-13，而非 `{value}`"
+        )),
+    }
+}
+
+pub fn resolve_args() -> Result<Vec<TestInfo>, ReaderError> {
     let args = Args::parse();
 
-    init_logger(if args.verbose {
-        log::LevelFilter::Debug
-    } else {
-        log::LevelFilter::Warn
-    });
+    init_logger(
+        if args.verbose {
+            log::LevelFilter::Debug
+        } else {
+            log::LevelFilter::Warn
+        },
+        args.log_file
+            .as_deref()
+            .map(|path| (Path::new(path), args.log_level)),
+    )
+    .map_err(|e| ReaderError::General(format!("無法建立記錄檔: {e}")))?;
 
     log::debug!("{:?}", &args);
 
-    if !file_exists(&args.file) {
-        return Err(ReaderError::FileNotFound(args.file));
+    let files = expand_files(&args.files)?;
+    if let Some(binary) = &args.binary
+        && !file_exists(binary)
+    {
+        return Err(ReaderError::FileNotFound(binary.clone()));
+    }
+    let shuffle_seed = args
+        .shuffle_cases
+        .then(|| args.seed.unwrap_or_else(random_seed));
+
+    let mut applied_rc = Vec::new();
+    files
+        .into_iter()
+        .map(|file| {
+            let mut info = test_info_for(&args, file, shuffle_seed)?;
+            if let Some((path, rc)) = find_project_rc(Path::new(&info.file))? {
+                if !applied_rc.contains(&path) {
+                    println!("⚙️ 套用專案設定檔 {}", path.display());
+                    applied_rc.push(path);
+                }
+                info.with_rc(&rc, &args);
+            }
+            Ok(info)
+        })
+        .collect()
+}
+
+/// 展開含有萬用字元的路徑 (例如 `sol_*.cpp`)，其餘路徑須為既有檔案。
+fn expand_files(patterns: &[String]) -> Result<Vec<String>, ReaderError> {
+    let mut files = Vec::new();
+    for pattern in patterns {
+        if !pattern.contains(['*', '?', '[']) {
+            if !file_exists(pattern) {
+                return Err(ReaderError::FileNotFound(pattern.clone()));
+            }
+            files.push(pattern.clone());
+            continue;
+        }
+
+        let matched: Vec<String> = glob::glob(pattern)
+            .map_err(|e| ReaderError::General(format!("無效的檔案樣式 {pattern}: {e}")))?
+            .filter_map(Result::ok)
+            .filter(|path| path.is_file())
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        if matched.is_empty() {
+            return Err(ReaderError::FileNotFound(pattern.clone()));
+        }
+        files.extend(matched);
+    }
+    Ok(files)
+}
+
+fn prebuilt_for(args: &Args, file: &str) -> Option<String> {
+    args.binary
+        .clone()
+        .or_else(|| args.no_compile.then(|| file.to_owned()))
+}
+
+/// 將成對的 `--xxx` / `--no-xxx` 旗標轉為 `Option<bool>`，兩者皆未指定時為 `None`。
+fn cli_flag(enabled: bool, disabled: bool) -> Option<bool> {
+    if enabled {
+        Some(true)
+    } else if disabled {
+        Some(false)
+    } else {
+        None
     }
+}
 
-    let file_type = match args.lang {
-        Some(i) => i,
-        None => match Path::new(&args.file).extension() {
+fn split_compile_args(args: &Args) -> Vec<String> {
+    args.compile_args
+        .as_deref()
+        .map(|compile_args| compile_args.split_whitespace().map(str::to_owned).collect())
+        .unwrap_or_default()
+}
+
+fn test_info_for(
+    args: &Args,
+    file: String,
+    shuffle_seed: Option<u64>,
+) -> Result<TestInfo, ReaderError> {
+    let file_type = match &args.lang {
+        Some(i) => i.clone(),
+        None => match Path::new(&file).extension() {
             Some(extension) => extension.to_string_lossy().into_owned(),
             None => "".to_owned(),
         },
     };
 
-    if args.no_judge {
+    let prebuilt = prebuilt_for(args, &file);
+
+    if args.no_judge || args.compile_only {
         Ok(TestInfo {
             file_type,
-            file: args.file,
+            file,
             cases: vec![],
             max_memory: None,
             max_time: None,
             do_judge: false,
+            compile_only: args.compile_only,
+            keep_going: args.keep_going,
+            bell: args.bell,
+            profile_phases: args.profile_phases,
+            only_failing: false,
+            tags: Vec::new(),
+            prebuilt,
             warmup_times: None,
+            capture_compile: args.capture_compile,
+            compile_args: split_compile_args(args),
+            program_overrides: args.lang_bins.clone(),
+            profile_output: None,
+            expected_verdict: None,
+            detect_empty_output: true,
+            extra_args: args.extra_args.clone(),
+            check_inputs: false,
+            validator: None,
+            input_file: None,
+            output_file: None,
+            compare_lines: None,
+            max_mismatch: None,
+            compare_mode: CompareMode::default(),
+            exact_input: None,
+            binary_io: false,
+            merged_cases: None,
+            stdin_prompt: args.stdin_prompt,
+            quiet: false,
+            memory_metric: args.memory_metric,
+            shuffle_seed: None,
+            repeat: 1,
+            max_diff_bytes: args.max_diff_bytes,
+            show_output: args.show_output,
+            explain_diff: args.explain_diff,
+            sandbox: args.sandbox,
+            number_grouping: args.no_group.then_some(false),
+            max_processes: None,
+            spawn_retries: args.spawn_retries,
+            unicode_form: args.unicode_normalize,
+            ignore_leading_whitespace: args.ignore_leading_whitespace,
+            trim_trailing: true,
+            presentation_error: cli_flag(args.presentation_error, args.no_presentation_error),
         })
     } else {
-        let config = read_test_cases(if let Some(config) = args.config {
-            TestCasePath::specified(config)
+        let mut config = if let (Some(input), Some(answer)) = (&args.stdin_file, &args.answer_file)
+        {
+            TestCases::from_files(input, answer)?
         } else {
-            TestCasePath::no_extension(change_extension(&args.file, ""))
-        })?;
+            read_test_cases(
+                if let Some(config) = &args.config {
+                    TestCasePath::specified(config)
+                } else {
+                    TestCasePath::no_extension(change_extension(&file, ""))
+                },
+                args.config_format,
+            )?
+        };
+
+        // 合併後的單一測資沒有標籤，因此先依標籤篩選再合併
+        let mut tags = args.tags.clone();
+        let merged_cases = if args.multi_test || config.multi_test == Some(true) {
+            if !tags.is_empty() {
+                config.cases.retain(|case| case.has_any_tag(&tags));
+                tags.clear();
+            }
+            Some(config.merge_into_multi_test()?)
+        } else {
+            None
+        };
 
         log::debug!("{:?}", &config);
 
@@ -102,12 +562,56 @@ pub fn resolve_args() -> Result<TestInfo, ReaderError> {
 
         Ok(TestInfo {
             file_type,
-            file: args.file,
+            file,
             cases: config.cases,
             max_memory: args.memory.or(config_limit.memory),
             max_time: args.time.or(config_limit.time).map(Duration::from_millis),
             do_judge: true,
+            compile_only: false,
+            keep_going: args.keep_going,
+            bell: args.bell,
+            profile_phases: args.profile_phases,
+            only_failing: args.only_failing,
+            tags,
+            prebuilt,
             warmup_times: args.warmup,
+            capture_compile: args.capture_compile,
+            compile_args: split_compile_args(args),
+            program_overrides: args.lang_bins.clone(),
+            profile_output: args.profile.clone(),
+            expected_verdict: args.expect,
+            detect_empty_output: true,
+            extra_args: args.extra_args.clone(),
+            check_inputs: args.check,
+            validator: args.validator.clone().or(config.validator),
+            input_file: args.input_file.clone().or(config.input_file),
+            output_file: args.output_file.clone().or(config.output_file),
+            compare_lines: args.compare_lines.or(config.compare_lines),
+            max_mismatch: args.max_mismatch.or(config.max_mismatch),
+            compare_mode: args
+                .compare_mode
+                .or(config.compare_mode)
+                .unwrap_or_default(),
+            exact_input: args.no_trim.then_some(true).or(config.exact_input),
+            binary_io: args.binary_io || config.binary_io == Some(true),
+            merged_cases,
+            stdin_prompt: false,
+            quiet: args.quiet,
+            memory_metric: args.memory_metric,
+            shuffle_seed,
+            repeat: args.repeat.unwrap_or(1),
+            max_diff_bytes: args.max_diff_bytes,
+            show_output: args.show_output,
+            explain_diff: args.explain_diff,
+            sandbox: args.sandbox,
+            number_grouping: args.no_group.then_some(false),
+            max_processes: None,
+            spawn_retries: args.spawn_retries,
+            unicode_form: args.unicode_normalize,
+            ignore_leading_whitespace: args.ignore_leading_whitespace
+                || config.trim_leading == Some(true),
+            trim_trailing: config.trim_trailing != Some(false),
+            presentation_error: cli_flag(args.presentation_error, args.no_presentation_error),
         })
     }
 }
@@ -119,13 +623,183 @@ pub struct TestInfo {
     pub max_memory: Option<usize>,
     pub max_time: Option<Duration>,
     pub do_judge: bool,
+    pub compile_only: bool,
+    pub keep_going: bool,
+    pub bell: Option<BellMode>,
+    pub profile_phases: bool,
+    pub only_failing: bool,
+    /// 只評測帶有其中任一標籤的測資，空白時評測所有測資。
+    pub tags: Vec<String>,
+    /// 略過編譯時直接執行的檔案。
+    pub prebuilt: Option<String>,
     pub warmup_times: Option<u32>,
+    pub capture_compile: bool,
+    /// 命令列指定的額外編譯參數。
+    pub compile_args: Vec<String>,
+    /// `--lang-bin` 指定的程式替換。
+    pub program_overrides: Vec<(String, String)>,
+    pub profile_output: Option<String>,
+    pub expected_verdict: Option<VerdictKind>,
+    pub detect_empty_output: bool,
+    pub extra_args: Vec<String>,
+    pub check_inputs: bool,
+    pub validator: Option<String>,
+    /// 以檔案而非 stdin/stdout 進行輸入輸出時使用的檔名。
+    pub input_file: Option<String>,
+    pub output_file: Option<String>,
+    pub compare_lines: Option<usize>,
+    pub max_mismatch: Option<usize>,
+    pub compare_mode: CompareMode,
+    /// 命令列與測資配置檔皆未指定時為 `None`，再由全域設定檔決定。
+    pub exact_input: Option<bool>,
+    pub binary_io: bool,
+    /// 合併為單一輸入前的測資數，未合併時為 `None`。
+    pub merged_cases: Option<usize>,
+    /// 未評測時先從終端機讀取輸入再執行程式。
+    pub stdin_prompt: bool,
+    /// 不逐筆顯示測資資訊，改以單行進度顯示。
+    pub quiet: bool,
+    pub memory_metric: Option<MemoryMetric>,
+    pub shuffle_seed: Option<u64>,
+    /// 每筆測資執行的次數。
+    pub repeat: u32,
+    pub max_diff_bytes: Option<usize>,
+    pub show_output: bool,
+    pub explain_diff: bool,
+    pub sandbox: bool,
+    pub number_grouping: Option<bool>,
+    pub max_processes: Option<usize>,
+    pub spawn_retries: Option<u32>,
+    pub unicode_form: Option<UnicodeForm>,
+    pub ignore_leading_whitespace: bool,
+    /// 比對前移除每一行結尾的空白。
+    pub trim_trailing: bool,
+    /// 未由命令列或專案設定檔指定時為 `None`，再由全域設定檔決定。
+    pub presentation_error: Option<bool>,
 }
 
 impl TestInfo {
+    /// 以專案設定檔補上命令列與測資配置檔皆未指定的選項。
+    fn with_rc(&mut self, rc: &ProjectRc, args: &Args) {
+        if self.max_time.is_none() {
+            self.max_time = rc.time.map(Duration::from_millis);
+        }
+        if self.max_memory.is_none() {
+            self.max_memory = rc.memory;
+        }
+        if self.warmup_times.is_none() {
+            self.warmup_times = rc.warmup;
+        }
+        if self.compile_args.is_empty()
+            && let Some(compile_args) = &rc.compile_args
+        {
+            self.compile_args = compile_args.split_whitespace().map(str::to_owned).collect();
+        }
+        if self.max_diff_bytes.is_none() {
+            self.max_diff_bytes = rc.max_diff_bytes;
+        }
+        if self.max_mismatch.is_none() {
+            self.max_mismatch = rc.max_mismatch;
+        }
+        if self.spawn_retries.is_none() {
+            self.spawn_retries = rc.spawn_retries;
+        }
+        // 命令列以 --no-* 明確關閉的選項不受專案設定檔影響
+        self.keep_going |= rc.keep_going && !args.no_keep_going;
+        self.capture_compile |= rc.capture_compile && !args.no_capture_compile;
+        self.sandbox |= rc.sandbox && !args.no_sandbox;
+        self.show_output |= rc.show_output && !args.no_show_output;
+        self.explain_diff |= rc.explain_diff && !args.no_explain_diff;
+        if self.presentation_error.is_none() {
+            self.presentation_error = rc.presentation_error;
+        }
+        self.ignore_leading_whitespace |=
+            rc.ignore_leading_whitespace && !args.no_ignore_leading_whitespace;
+    }
+
     pub fn with_config(&mut self, config: &EvaluatorConfig) {
         if self.warmup_times.is_none() {
             self.warmup_times = config.warmup;
         }
+        if self.max_diff_bytes.is_none() {
+            self.max_diff_bytes = config.max_diff_bytes;
+        }
+        if let Some(limits) = &config.default_limits {
+            if self.max_memory.is_none() {
+                self.max_memory = limits.memory;
+            }
+            if self.max_time.is_none() {
+                self.max_time = limits.time.map(Duration::from_millis);
+            }
+        }
+        self.max_processes = config.max_processes;
+        if self.spawn_retries.is_none() {
+            self.spawn_retries = config.spawn_retries;
+        }
+        if self.number_grouping.is_none() {
+            self.number_grouping = config.number_grouping;
+        }
+        if let Some(detect) = config.detect_empty_output {
+            self.detect_empty_output = detect;
+        }
+        if self.memory_metric.is_none() {
+            self.memory_metric = config.memory_metric;
+        }
+        if self.presentation_error.is_none() {
+            self.presentation_error = config.presentation_error;
+        }
+        if self.exact_input.is_none() {
+            self.exact_input = config.exact_input;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_negation_overrides_rc() {
+        let args = Args::try_parse_from(["evaluator", "-n", "--no-keep-going", "a.cpp"]).unwrap();
+        let mut info = test_info_for(&args, "a.cpp".to_owned(), None).unwrap();
+        let rc = ProjectRc {
+            keep_going: true,
+            show_output: true,
+            ..Default::default()
+        };
+        info.with_rc(&rc, &args);
+        assert!(!info.keep_going);
+        assert!(info.show_output);
+
+        let args =
+            Args::try_parse_from(["evaluator", "--no-sandbox", "--sandbox", "a.cpp"]).unwrap();
+        assert!(args.sandbox);
+        assert!(!args.no_sandbox);
+    }
+
+    #[test]
+    fn test_global_config_is_lowest_default() {
+        let config: EvaluatorConfig =
+            serde_yml::from_str("languages: []\npresentation_error: true\n").unwrap();
+        let rc = ProjectRc {
+            presentation_error: Some(false),
+            ..Default::default()
+        };
+
+        let args = Args::try_parse_from(["evaluator", "-n", "a.cpp"]).unwrap();
+        let mut info = test_info_for(&args, "a.cpp".to_owned(), None).unwrap();
+        info.with_config(&config);
+        assert_eq!(info.presentation_error, Some(true));
+
+        let mut info = test_info_for(&args, "a.cpp".to_owned(), None).unwrap();
+        info.with_rc(&rc, &args);
+        info.with_config(&config);
+        assert_eq!(info.presentation_error, Some(false));
+
+        let args =
+            Args::try_parse_from(["evaluator", "-n", "--no-presentation-error", "a.cpp"]).unwrap();
+        let mut info = test_info_for(&args, "a.cpp".to_owned(), None).unwrap();
+        info.with_config(&config);
+        assert_eq!(info.presentation_error, Some(false));
     }
 }
@@ -2,7 +2,9 @@ use serde::Deserialize;
 use shared::get_config_path;
 use std::fs;
 
+use crate::monitor::MemoryMetric;
 use crate::reader::error::ReaderError;
+use crate::reader::test_cases::LimitInfo;
 
 #[derive(Debug, Deserialize)]
 struct ConfigRoot {
@@ -13,15 +15,41 @@ struct ConfigRoot {
 pub struct EvaluatorConfig {
     pub languages: Vec<LanguageProfile>,
     pub warmup: Option<u32>,
+    pub detect_empty_output: Option<bool>,
+    pub max_diff_bytes: Option<usize>,
+    pub number_grouping: Option<bool>,
+    /// 命令列與測資檔皆未設定限制時使用的預設值，優先於內建的 2 秒 / 1 GiB。
+    pub default_limits: Option<LimitInfo>,
+    /// 受測程式可同時存在的程序數量上限，Linux 的執行緒也計入 (macOS 不支援)。
+    pub max_processes: Option<usize>,
+    /// 啟動受測程式因系統資源暫時不足而失敗時的重試次數。
+    pub spawn_retries: Option<u32>,
+    /// 輸出僅空白或換行與答案不同時判為 PE (Presentation Error) 而非 WA。
+    pub presentation_error: Option<bool>,
+    /// 回報的記憶體指標，見 [`MemoryMetric`]。
+    pub memory_metric: Option<MemoryMetric>,
+    /// 原樣寫入測資輸入；未設定時會在輸入缺少結尾換行時補上一個。
+    pub exact_input: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct LanguageProfile {
     pub extension: String,
+    /// 同樣使用此設定的其他副檔名 (例如 C++ 的 `cc`、`cxx`)。
+    #[serde(default)]
+    pub aliases: Vec<String>,
     pub compile: Option<CommandInstruction>,
+    /// 額外的編譯參數，取代編譯指令中的 `{compile_args}`；指令中沒有此佔位符時附加於結尾。
+    pub compile_args: Option<Vec<String>>,
     pub run: Option<CommandInstruction>,
 }
 
+impl LanguageProfile {
+    pub fn matches(&self, extension: &str) -> bool {
+        self.extension == extension || self.aliases.iter().any(|alias| alias == extension)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CommandInstruction {
     pub command: String,
@@ -31,8 +59,12 @@ pub fn read_config() -> Result<EvaluatorConfig, ReaderError> {
     let config_path = get_config_path().map_err(|e| ReaderError::General(e.to_string()))?;
     let config_contents = fs::read_to_string(&config_path)
         .map_err(|_| ReaderError::FileNotFound(config_path.to_string_lossy().to_string()))?;
-    let root: ConfigRoot = serde_yml::from_str(&config_contents)
-        .map_err(|e| ReaderError::General(format!("Failed to read {}: {}", config_path.display(), e)))?;
+    let root: ConfigRoot = if config_path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&config_contents).map_err(|e| e.to_string())
+    } else {
+        serde_yml::from_str(&config_contents).map_err(|e| e.to_string())
+    }
+    .map_err(|e| ReaderError::General(format!("Failed to read {}: {}", config_path.display(), e)))?;
     log::debug!("{:?}", root.evaluator);
     Ok(root.evaluator)
 }
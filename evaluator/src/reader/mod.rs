@@ -1,8 +1,10 @@
 mod args;
 mod configure;
 mod error;
+mod rc;
 mod test_cases;
 mod utils;
-pub use args::{TestInfo, resolve_args};
+pub use args::{BellMode, TestInfo, resolve_args};
 pub use configure::{EvaluatorConfig, LanguageProfile, read_config};
+pub use test_cases::TestCase;
 pub use utils::ensure_dir_exists;
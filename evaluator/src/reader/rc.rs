@@ -0,0 +1,73 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::{self, Path, PathBuf};
+
+use super::error::ReaderError;
+use super::utils::file_exists;
+
+/// 專案設定檔的檔名，依序搜尋；JSON 為 YAML 的子集，因此以同一個解析器讀取。
+const RC_FILE_NAMES: [&str; 3] = [".ojrc.yaml", ".ojrc.yml", ".ojrc.json"];
+
+/// 專案設定檔 (`.ojrc.yaml`)，提供評測選項的預設值，鍵名與命令列選項相同。
+///
+/// 優先順序由低至高為: 全域 config.yaml < 專案設定檔 < 測資配置檔 < 命令列。
+/// 在此開啟的布林選項可於命令列以對應的 `--no-*` 關閉，例如 `--no-keep-going`。
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ProjectRc {
+    pub time: Option<u64>,
+    pub memory: Option<usize>,
+    pub warmup: Option<u32>,
+    pub compile_args: Option<String>,
+    pub max_diff_bytes: Option<usize>,
+    pub max_mismatch: Option<usize>,
+    pub spawn_retries: Option<u32>,
+    #[serde(default)]
+    pub keep_going: bool,
+    #[serde(default)]
+    pub capture_compile: bool,
+    #[serde(default)]
+    pub sandbox: bool,
+    #[serde(default)]
+    pub show_output: bool,
+    #[serde(default)]
+    pub explain_diff: bool,
+    pub presentation_error: Option<bool>,
+    #[serde(default)]
+    pub ignore_leading_whitespace: bool,
+}
+
+/// 從原始碼所在的資料夾往上層尋找專案設定檔，回傳最近的一個。
+pub fn find_project_rc(source: &Path) -> Result<Option<(PathBuf, ProjectRc)>, ReaderError> {
+    let source = path::absolute(source).map_err(|e| ReaderError::General(e.to_string()))?;
+    let Some(path) = source
+        .ancestors()
+        .skip(1)
+        .flat_map(|dir| RC_FILE_NAMES.map(|name| dir.join(name)))
+        .find(|path| file_exists(path))
+    else {
+        return Ok(None);
+    };
+
+    let content = fs::read_to_string(&path)
+        .map_err(|_| ReaderError::FileNotFound(path.to_string_lossy().into_owned()))?;
+    let rc = serde_yml::from_str(&content)
+        .map_err(|e| ReaderError::General(format!("{} 格式錯誤: {e}", path.display())))?;
+    Ok(Some((path, rc)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rc_uses_cli_option_names() {
+        let rc: ProjectRc =
+            serde_yml::from_str("time: 2000\nwarmup: 3\nkeep-going: true\n").unwrap();
+        assert_eq!(rc.time, Some(2000));
+        assert_eq!(rc.warmup, Some(3));
+        assert!(rc.keep_going);
+        assert!(serde_yml::from_str::<ProjectRc>("keep_going: true\n").is_err());
+        assert!(serde_yml::from_str::<ProjectRc>(r#"{"memory": 262144}"#).is_ok());
+    }
+}
@@ -1,43 +1,126 @@
+use clap::ValueEnum;
 use serde::Deserialize;
-use std::fs;
 use std::path::{Path, PathBuf};
+use std::{fs, iter, mem};
 
 use super::error::ReaderError;
+use super::utils::strip_bom;
+use crate::judge::CompareMode;
 
-pub fn read_test_cases(path: TestCasePath) -> Result<TestCases, ReaderError> {
+/// 配置檔格式，未指定時依副檔名判斷。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ConfigFormat {
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// 自動尋找配置檔時依序嘗試的副檔名。
+    const SEARCH_ORDER: [(&str, Self); 3] = [
+        ("yaml", Self::Yaml),
+        ("yml", Self::Yaml),
+        ("toml", Self::Toml),
+    ];
+
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Yaml,
+        }
+    }
+}
+
+pub fn read_test_cases(
+    path: TestCasePath,
+    format: Option<ConfigFormat>,
+) -> Result<TestCases, ReaderError> {
     let path = match path {
         TestCasePath::Specified(p) => p,
-        TestCasePath::NoExtension(p) => resolve_yaml_path(p)?,
+        TestCasePath::NoExtension(p) => resolve_config_path(p, format)?,
+    };
+    let mut cases = match format.unwrap_or_else(|| ConfigFormat::from_path(&path)) {
+        ConfigFormat::Yaml => read_test_cases_yaml(&path)?,
+        ConfigFormat::Toml => read_test_cases_toml(&path)?,
     };
-    let raw_str = fs::read_to_string(&path)
-        .map_err(|_| ReaderError::FileNotFound(path.to_string_lossy().into_owned()))?;
 
-    let cases: TestCases =
-        serde_yml::from_str(&raw_str).map_err(|e| ReaderError::General(e.to_string()))?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    for case in &mut cases.cases {
+        case.load_files(base_dir)?;
+    }
+
+    for (index, case) in cases.cases.iter_mut().enumerate() {
+        if case.remove_bom() {
+            log::warn!("測資 {} 含有 BOM，已自動移除", index + 1);
+        }
+    }
+
+    if let Some(index) = cases
+        .cases
+        .iter()
+        .position(|case| case.answers().is_empty())
+    {
+        return Err(ReaderError::General(format!(
+            "測資 {} 缺少 answer 或 answers 欄位",
+            index + 1
+        )));
+    }
 
     Ok(cases)
 }
 
-fn resolve_yaml_path<P: AsRef<Path>>(base_path: P) -> Result<PathBuf, ReaderError> {
-    let base = base_path.as_ref();
+fn read_config_str(path: &Path) -> Result<String, ReaderError> {
+    fs::read_to_string(path)
+        .map_err(|_| ReaderError::FileNotFound(path.to_string_lossy().into_owned()))
+}
 
-    let yml_path = base.with_extension("yml");
-    let yaml_path = base.with_extension("yaml");
+fn read_test_cases_yaml(path: &Path) -> Result<TestCases, ReaderError> {
+    serde_yml::from_str(&read_config_str(path)?).map_err(|e| ReaderError::General(e.to_string()))
+}
 
-    let yml_exists = yml_path.exists();
-    let yaml_exists = yaml_path.exists();
+pub fn read_test_cases_toml(path: &Path) -> Result<TestCases, ReaderError> {
+    toml::from_str(&read_config_str(path)?).map_err(|e| ReaderError::General(e.to_string()))
+}
 
-    match (yml_exists, yaml_exists) {
-        (true, false) => Ok(yml_path),
-        (false, true) => Ok(yaml_path),
-        (true, true) => Err(ReaderError::FileNotFound(format!(
-            "配置檔衝突：同時存在 {} 和 {}",
-            yml_path.display(),
-            yaml_path.display()
-        ))),
-        (false, false) => Err(ReaderError::NoConfigFile(
-            yaml_path.to_string_lossy().into_owned(),
-        )),
+/// 依 `.yaml`、`.yml`、`.toml` 的順序尋找配置檔，同時存在多個時使用優先的一個並發出警告。
+fn resolve_config_path<P: AsRef<Path>>(
+    base_path: P,
+    format: Option<ConfigFormat>,
+) -> Result<PathBuf, ReaderError> {
+    let base = base_path.as_ref();
+
+    let candidates: Vec<PathBuf> = ConfigFormat::SEARCH_ORDER
+        .into_iter()
+        .filter(|&(_, candidate)| format.is_none_or(|format| format == candidate))
+        .map(|(extension, _)| base.with_extension(extension))
+        .filter(|path| path.exists())
+        .collect();
+
+    match candidates.as_slice() {
+        [] => {
+            let extension = if format == Some(ConfigFormat::Toml) {
+                "toml"
+            } else {
+                "yaml"
+            };
+            Err(ReaderError::NoConfigFile(
+                base.with_extension(extension)
+                    .to_string_lossy()
+                    .into_owned(),
+            ))
+        }
+        [path] => Ok(path.clone()),
+        [path, others @ ..] => {
+            log::warn!(
+                "同時存在多個配置檔，使用 {} (忽略 {})",
+                path.display(),
+                others
+                    .iter()
+                    .map(|other| other.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join("、")
+            );
+            Ok(path.clone())
+        }
     }
 }
 
@@ -56,16 +139,184 @@ impl TestCasePath {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct TestCases {
     pub cases: Vec<TestCase>,
     pub limit: Option<LimitInfo>,
+    /// 驗證測資輸入格式的指令，輸入由 stdin 餵入，結束代碼 0 表示格式正確。
+    pub validator: Option<String>,
+    /// 程式從工作目錄中的此檔案讀取輸入，而非 stdin。
+    pub input_file: Option<String>,
+    /// 程式將輸出寫入工作目錄中的此檔案，而非 stdout。
+    pub output_file: Option<String>,
+    /// 只比對輸出的前幾行，適用於答案之後允許輸出除錯資訊的題目。
+    pub compare_lines: Option<usize>,
+    /// 不同的行數 (或 token 數) 不超過此值時仍判為 AC，用於部分給分的題目。
+    pub max_mismatch: Option<usize>,
+    /// 判定方式，見 [`CompareMode`]。
+    pub compare_mode: Option<CompareMode>,
+    /// 原樣寫入輸入；未設定時會在輸入缺少結尾換行時補上一個。
+    pub exact_input: Option<bool>,
+    /// 以位元組比對輸出與答案，適用於輸出非文字資料的題目。
+    pub binary_io: Option<bool>,
+    /// 將所有測資合併為一次執行，見 [`TestCases::merge_into_multi_test`]。
+    pub multi_test: Option<bool>,
+    /// 比對前移除每一行開頭的空白，適用於不在意縮排的題目 (預設關閉)。
+    pub trim_leading: Option<bool>,
+    /// 比對前移除每一行結尾的空白 (預設開啟)，關閉時行尾空白也須與答案相同。
+    pub trim_trailing: Option<bool>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct TestCase {
+    #[serde(default)]
     pub input: String,
-    pub answer: String,
+    pub answer: Option<String>,
+    /// 其他可接受的答案，輸出符合任一答案即為正確。
+    #[serde(default)]
+    pub answers: Vec<String>,
+    /// 執行此測資時附加於程式的命令列參數。
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 範例測資 (題目中公開給參賽者的測資)，報告中與隱藏測資分開統計。
+    #[serde(default)]
+    pub is_sample: bool,
+    /// 自訂標籤 (如 edge、large)，可用 `--tag` 只評測特定標籤的測資。
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 從檔案讀取輸入，路徑相對於配置檔所在的資料夾。
+    pub input_path: Option<String>,
+    /// 從檔案讀取答案，路徑相對於配置檔所在的資料夾。
+    pub answer_path: Option<String>,
+    /// 由 `input_path` 讀入的原始位元組。
+    #[serde(skip)]
+    raw_input: Option<Vec<u8>>,
+    /// 由 `answer_path` 讀入的原始位元組。
+    #[serde(skip)]
+    raw_answer: Option<Vec<u8>>,
+}
+
+impl TestCase {
+    /// 所有可接受的答案，`answer` 優先於 `answers`。
+    pub fn answers(&self) -> Vec<&str> {
+        self.answer
+            .iter()
+            .chain(self.answers.iter())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// 是否帶有 `tags` 中任一標籤，範例測資視為帶有 `sample` 標籤。
+    pub fn has_any_tag(&self, tags: &[String]) -> bool {
+        tags.iter()
+            .any(|tag| self.tags.contains(tag) || (self.is_sample && tag == "sample"))
+    }
+
+    /// 以位元組模式評測時寫入程式的輸入，由檔案讀入的輸入保留原始內容 (含 BOM)。
+    pub fn input_bytes(&self) -> &[u8] {
+        self.raw_input.as_deref().unwrap_or(self.input.as_bytes())
+    }
+
+    /// 以位元組模式評測時所有可接受的答案，由檔案讀入的答案保留原始內容 (含 BOM)。
+    pub fn answer_bytes(&self) -> Vec<&[u8]> {
+        match &self.raw_answer {
+            Some(raw) => iter::once(raw.as_slice())
+                .chain(self.answers.iter().map(String::as_bytes))
+                .collect(),
+            None => self.answers().into_iter().map(str::as_bytes).collect(),
+        }
+    }
+
+    /// 讀取 `input_path` 與 `answer_path` 指定的檔案，文字欄位存放其 UTF-8 解讀結果供顯示與文字比對。
+    fn load_files(&mut self, base_dir: &Path) -> Result<(), ReaderError> {
+        let read = |path: &str| {
+            let path = base_dir.join(path);
+            fs::read(&path)
+                .map_err(|_| ReaderError::FileNotFound(path.to_string_lossy().into_owned()))
+        };
+        if let Some(path) = &self.input_path {
+            let bytes = read(path)?;
+            self.input = String::from_utf8_lossy(&bytes).into_owned();
+            self.raw_input = Some(bytes);
+        }
+        if let Some(path) = &self.answer_path {
+            let bytes = read(path)?;
+            self.answer = Some(String::from_utf8_lossy(&bytes).into_owned());
+            self.raw_answer = Some(bytes);
+        }
+        Ok(())
+    }
+
+    /// 移除輸入與答案開頭的 BOM，回傳是否有任何欄位含有 BOM。
+    fn remove_bom(&mut self) -> bool {
+        let mut found = false;
+        let fields = iter::once(&mut self.input)
+            .chain(self.answer.as_mut())
+            .chain(self.answers.iter_mut());
+        for field in fields {
+            if field.starts_with('\u{feff}') {
+                *field = strip_bom(mem::take(field));
+                found = true;
+            }
+        }
+        found
+    }
+}
+
+impl TestCases {
+    /// 將所有測資合併為「第一行為測資數 T」的單一輸入，答案依序串接，用於一次讀入多筆測資的題目。
+    ///
+    /// 每筆測資只取第一個答案；回傳合併前的測資數。
+    pub fn merge_into_multi_test(&mut self) -> Result<usize, ReaderError> {
+        let count = self.cases.len();
+        for (index, case) in self.cases.iter().enumerate() {
+            if !case.args.is_empty() || case.input_path.is_some() || case.answer_path.is_some() {
+                return Err(ReaderError::General(format!(
+                    "測資 {} 設定了 args 或 input_path/answer_path，無法合併為單一輸入",
+                    index + 1
+                )));
+            }
+            if case.answers().len() > 1 {
+                return Err(ReaderError::General(format!(
+                    "測資 {} 設定了多個答案，無法合併為單一輸入",
+                    index + 1
+                )));
+            }
+        }
+
+        let mut input = format!("{count}\n");
+        let mut answer = String::new();
+        for case in &self.cases {
+            input.push_str(case.input.trim_end_matches(['\r', '\n']));
+            input.push('\n');
+            let first_answer = case.answers().first().copied().unwrap_or_default();
+            answer.push_str(first_answer.trim_end());
+            answer.push('\n');
+        }
+        let is_sample = self.cases.iter().all(|case| case.is_sample);
+        self.cases = vec![TestCase {
+            input,
+            answer: Some(answer),
+            is_sample,
+            ..Default::default()
+        }];
+        Ok(count)
+    }
+
+    /// 以輸入檔與答案檔建立只有單筆測資的配置，不讀取任何配置檔。
+    pub fn from_files(input_path: &str, answer_path: &str) -> Result<Self, ReaderError> {
+        let mut case = TestCase {
+            input_path: Some(input_path.to_owned()),
+            answer_path: Some(answer_path.to_owned()),
+            ..Default::default()
+        };
+        case.load_files(Path::new(""))?;
+        case.remove_bom();
+        Ok(Self {
+            cases: vec![case],
+            ..Default::default()
+        })
+    }
 }
 
 #[derive(Deserialize, Debug, Default)]
@@ -73,3 +324,56 @@ pub struct LimitInfo {
     pub memory: Option<usize>,
     pub time: Option<u64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_uses_yaml_field_names() {
+        let toml = r#"
+compare_lines = 1
+
+[limit]
+time = 1000
+
+[[cases]]
+input = "1 2"
+answer = "3"
+
+[[cases]]
+input = "2 2"
+answers = ["4", "4.0"]
+"#;
+        let cases: TestCases = toml::from_str(toml).unwrap();
+        assert_eq!(cases.cases.len(), 2);
+        assert_eq!(cases.cases[1].answers(), ["4", "4.0"]);
+        assert_eq!(cases.limit.and_then(|limit| limit.time), Some(1000));
+        assert_eq!(cases.compare_lines, Some(1));
+    }
+
+    #[test]
+    fn test_merge_into_multi_test_prefixes_case_count() {
+        let yaml = "cases:\n- input: \"1 2\\n\"\n  answer: \"3\\n\"\n- input: \"2 2\"\n  answers: [\"4\"]\n";
+        let mut cases: TestCases = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(cases.merge_into_multi_test().unwrap(), 2);
+        assert_eq!(cases.cases.len(), 1);
+        assert_eq!(cases.cases[0].input, "2\n1 2\n2 2\n");
+        assert_eq!(cases.cases[0].answers(), ["3\n4\n"]);
+
+        let yaml = "cases:\n- input: \"2 2\"\n  answers: [\"4\", \"4.0\"]\n";
+        let mut cases: TestCases = serde_yml::from_str(yaml).unwrap();
+        assert!(cases.merge_into_multi_test().is_err());
+    }
+
+    #[test]
+    fn test_has_any_tag() {
+        let yaml = "cases:\n- input: '1'\n  is_sample: true\n- input: '2'\n  tags: [edge, large]\n";
+        let cases: TestCases = serde_yml::from_str(yaml).unwrap();
+        let tags = |names: &[&str]| names.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(cases.cases[0].has_any_tag(&tags(&["sample"])));
+        assert!(!cases.cases[0].has_any_tag(&tags(&["edge"])));
+        assert!(cases.cases[1].has_any_tag(&tags(&["small", "large"])));
+        assert!(!cases.cases[1].has_any_tag(&[]));
+    }
+}
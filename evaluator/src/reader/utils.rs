@@ -10,6 +10,14 @@ pub fn change_extension<P: AsRef<Path>>(path: P, new_extension: &str) -> PathBuf
     path
 }
 
+/// 移除字串開頭的 UTF-8 BOM (`\u{FEFF}`)。
+pub fn strip_bom(s: String) -> String {
+    match s.strip_prefix('\u{feff}') {
+        Some(rest) => rest.to_owned(),
+        None => s,
+    }
+}
+
 pub fn file_exists<P: AsRef<Path>>(path: P) -> bool {
     let path = Path::new(path.as_ref());
     path.exists() && path.is_file()
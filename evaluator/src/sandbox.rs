@@ -0,0 +1,124 @@
+use std::io;
+use std::process::{Command, ExitStatus};
+
+/// 為 `runner` 套用 seccomp 沙箱，禁止網路連線、建立子程序等危險的系統呼叫。
+///
+/// 受測程式呼叫被禁止的系統呼叫時會被 `SIGSYS` 終止，可用 [`is_violation`] 判斷。
+/// 執行緒 (`clone` 帶有 `CLONE_THREAD`) 不受影響，因此 Java、Go 等多執行緒執行環境仍可運作。
+#[cfg(target_os = "linux")]
+pub fn apply_sandbox(runner: &mut Command) -> io::Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    let filters = linux::build_filters()?;
+    // SAFETY: 閉包只呼叫 prctl/seccomp，不配置記憶體，可在 fork 後安全執行。
+    unsafe {
+        runner.pre_exec(move || {
+            for filter in &filters {
+                seccompiler::apply_filter(filter)
+                    .map_err(|_| io::Error::from_raw_os_error(libc::EPERM))?;
+            }
+            Ok(())
+        })
+    };
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn apply_sandbox(_runner: &mut Command) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "沙箱功能僅支援 Linux",
+    ))
+}
+
+/// 判斷程式是否因呼叫沙箱禁止的系統呼叫而被終止。
+#[cfg(target_os = "linux")]
+pub fn is_violation(status: &ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+
+    status.signal() == Some(libc::SIGSYS)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn is_violation(_status: &ExitStatus) -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::collections::BTreeMap;
+    use std::env::consts::ARCH;
+    use std::io;
+
+    use seccompiler::{
+        BpfProgram, SeccompAction, SeccompCmpArgLen, SeccompCmpOp, SeccompCondition, SeccompFilter,
+        SeccompRule, TargetArch,
+    };
+
+    /// 無條件禁止的系統呼叫。
+    const DENIED_SYSCALLS: &[libc::c_long] = &[
+        libc::SYS_socket,
+        libc::SYS_socketpair,
+        libc::SYS_connect,
+        libc::SYS_bind,
+        libc::SYS_listen,
+        libc::SYS_accept,
+        libc::SYS_accept4,
+        #[cfg(target_arch = "x86_64")]
+        libc::SYS_fork,
+        #[cfg(target_arch = "x86_64")]
+        libc::SYS_vfork,
+        libc::SYS_ptrace,
+        libc::SYS_mount,
+        libc::SYS_umount2,
+        libc::SYS_reboot,
+        libc::SYS_setuid,
+        libc::SYS_setgid,
+    ];
+
+    pub fn build_filters() -> io::Result<Vec<BpfProgram>> {
+        let arch = TargetArch::try_from(ARCH).map_err(io::Error::other)?;
+
+        let mut denied: BTreeMap<i64, Vec<SeccompRule>> = DENIED_SYSCALLS
+            .iter()
+            .map(|&syscall| (syscall, vec![]))
+            .collect();
+        // 不帶 CLONE_THREAD 的 clone 等同於 fork
+        denied.insert(
+            libc::SYS_clone,
+            vec![
+                SeccompRule::new(vec![
+                    SeccompCondition::new(
+                        0,
+                        SeccompCmpArgLen::Qword,
+                        SeccompCmpOp::MaskedEq(libc::CLONE_THREAD as u64),
+                        0,
+                    )
+                    .map_err(io::Error::other)?,
+                ])
+                .map_err(io::Error::other)?,
+            ],
+        );
+        let deny = SeccompFilter::new(
+            denied,
+            SeccompAction::Allow,
+            SeccompAction::KillProcess,
+            arch,
+        )
+        .map_err(io::Error::other)?;
+
+        // clone3 的旗標位於指標指向的結構中無法檢查，回傳 ENOSYS 讓 libc 改用 clone
+        let fallback = SeccompFilter::new(
+            BTreeMap::from([(libc::SYS_clone3, vec![])]),
+            SeccompAction::Allow,
+            SeccompAction::Errno(libc::ENOSYS as u32),
+            arch,
+        )
+        .map_err(io::Error::other)?;
+
+        [deny, fallback]
+            .into_iter()
+            .map(|filter| BpfProgram::try_from(filter).map_err(io::Error::other))
+            .collect()
+    }
+}
@@ -1,31 +1,137 @@
 use num_format::ToFormattedString;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use std::borrow::Cow;
+use std::sync::atomic::Ordering;
+use unicode_width::UnicodeWidthStr;
 
-use crate::config::NUMBER_FORMAT;
+use crate::config::{NUMBER_FORMAT, NUMBER_GROUPING};
 
 pub const TEMP_FILE_EXE: &str = "output.exe";
 
+/// 計算字串在終端機上的顯示寬度，忽略 ANSI 樣式序列並將全形字元計為兩格。
+pub fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut rest = text;
+    while let Some(start) = rest.find('\x1b') {
+        width += rest[..start].width();
+        rest = &rest[start..];
+        match rest.find('m') {
+            Some(end) => rest = &rest[end + 1..],
+            None => return width,
+        }
+    }
+    width + rest.width()
+}
+
+/// 將已套用樣式的字串截斷至 `max_bytes` 位元組內，避免切斷字元或 ANSI 序列，並附上截斷提示。
+pub fn truncate_styled(text: &str, max_bytes: usize) -> Cow<'_, str> {
+    if text.len() <= max_bytes {
+        return Cow::Borrowed(text);
+    }
+
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut kept = &text[..end];
+    if let Some(escape) = kept.rfind('\x1b')
+        && !kept[escape..].contains('m')
+    {
+        kept = &kept[..escape];
+    }
+
+    Cow::Owned(format!(
+        "{kept}\x1b[0m\n... (輸出過長，已截斷，共 {} bytes)",
+        text.len().prettify()
+    ))
+}
+
 pub fn center_text(text: &str, total_length: usize, placeholder: &str) -> String {
-    let text_length = text.len();
+    let text_length = display_width(text);
     if text_length >= total_length {
         return text.to_string();
     }
 
-    let padding_length = (total_length - text_length) / 2;
+    let placeholder_width = display_width(placeholder).max(1);
+    let padding_length = (total_length - text_length) / 2 / placeholder_width;
     let left_padding = placeholder.repeat(padding_length);
-    let right_padding = placeholder.repeat(total_length - text_length - padding_length);
+    let right_padding =
+        placeholder.repeat((total_length - text_length) / placeholder_width - padding_length);
 
     format!("{left_padding} {text} {right_padding}")
 }
 
+/// 產生測資的執行順序 (原始索引)；提供 `shuffle_seed` 時以該種子打亂順序。
+pub fn case_order(len: usize, shuffle_seed: Option<u64>) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    if let Some(seed) = shuffle_seed {
+        order.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+    order
+}
+
+/// 產生隨機種子，用於未指定 `--seed` 時打亂測資。
+pub fn random_seed() -> u64 {
+    rand::rng().random()
+}
+
 pub trait PrettyNumber {
     fn prettify(&self) -> String;
 }
 
 impl<T> PrettyNumber for T
 where
-    T: ToFormattedString,
+    T: ToFormattedString + ToString,
 {
     fn prettify(&self) -> String {
-        self.to_formatted_string(&NUMBER_FORMAT)
+        if NUMBER_GROUPING.load(Ordering::Relaxed) {
+            self.to_formatted_string(&NUMBER_FORMAT)
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_order_is_reproducible() {
+        assert_eq!(case_order(4, None), vec![0, 1, 2, 3]);
+        assert_eq!(case_order(20, Some(42)), case_order(20, Some(42)));
+
+        let mut shuffled = case_order(20, Some(7));
+        shuffled.sort_unstable();
+        assert_eq!(shuffled, case_order(20, None));
+    }
+
+    #[test]
+    fn test_truncate_styled() {
+        assert_eq!(truncate_styled("short", 10), "short");
+
+        let truncated = truncate_styled("測資測資", 7);
+        assert!(truncated.starts_with("測資\x1b[0m\n"));
+
+        let truncated = truncate_styled("ab\x1b[31mcd", 5);
+        assert!(truncated.starts_with("ab\x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_display_width_cjk() {
+        assert_eq!(display_width("測資"), 4);
+        assert_eq!(display_width("ab"), 2);
+    }
+
+    #[test]
+    fn test_display_width_ignores_ansi() {
+        assert_eq!(display_width("\x1b[31m測資\x1b[0m"), 4);
+    }
+
+    #[test]
+    fn test_center_text_wide_text_aligns() {
+        let ascii = center_text("Input", 30, "-");
+        let wide = center_text("輸入資料", 30, "-");
+        assert_eq!(display_width(&ascii), display_width(&wide));
     }
 }
@@ -0,0 +1,60 @@
+use shared::build_native_shell_command;
+use std::io::{self, Write};
+use std::process::Stdio;
+
+use crate::reader::TestCase;
+
+/// 單筆測資的驗證結果。
+pub enum ValidationResult {
+    Valid,
+    Invalid(String),
+}
+
+/// 將測資輸入餵給驗證程式，結束代碼為 0 表示輸入格式正確。
+pub fn validate_input(command: &str, input: &str) -> io::Result<ValidationResult> {
+    let mut child = build_native_shell_command(command)?
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // 驗證程式常在發現錯誤後就結束而不讀完輸入，此時以結束代碼判斷
+    if let Some(mut stdin) = child.stdin.take()
+        && let Err(e) = stdin.write_all(input.as_bytes())
+        && e.kind() != io::ErrorKind::BrokenPipe
+    {
+        return Err(e);
+    }
+
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(ValidationResult::Valid);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let message = [stderr.trim(), stdout.trim()]
+        .into_iter()
+        .find(|msg| !msg.is_empty())
+        .map_or_else(|| output.status.to_string(), str::to_owned);
+
+    Ok(ValidationResult::Invalid(message))
+}
+
+/// 驗證所有測資的輸入並輸出報告。
+///
+/// # Returns
+/// 格式錯誤的測資數量。
+pub fn check_cases(command: &str, cases: &[TestCase]) -> io::Result<usize> {
+    let mut invalid_count = 0;
+    for (index, case) in cases.iter().enumerate() {
+        match validate_input(command, &case.input)? {
+            ValidationResult::Valid => println!("✅ 測資 {} 輸入格式正確", index + 1),
+            ValidationResult::Invalid(msg) => {
+                invalid_count += 1;
+                println!("❌ 測資 {} 輸入格式錯誤: {msg}", index + 1);
+            }
+        }
+    }
+    Ok(invalid_count)
+}
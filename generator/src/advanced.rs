@@ -5,7 +5,7 @@ use crate::{error, escapable, info, warn};
 use inquire::ui::{Color, RenderConfig, StyleSheet};
 use inquire::{Confirm, InquireError, Select, Text};
 use owo_colors::OwoColorize;
-use shared::{build_native_shell_command, get_exe_dir};
+use shared::{build_native_shell_command_in, get_exe_dir};
 use std::fmt::Display;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::process::Stdio;
@@ -42,8 +42,7 @@ pub fn prompt_advanced_options(
     // SAFE `unwrap`: `plugins` are retrieved from config, which is loaded via exe_dir.
     let exe_path = get_exe_dir().unwrap();
 
-    let mut child = build_native_shell_command(&ext.command)?
-        .current_dir(exe_path)
+    let mut child = build_native_shell_command_in(&ext.command, &exe_path)?
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -136,8 +135,9 @@ pub fn prompt_advanced_options(
     match child.wait() {
         Ok(status) => {
             if status.success() {
-                let suite = parse_easy_test_suite(&result_output);
-                Ok(Some(suite))
+                let parsed = parse_easy_test_suite(&result_output);
+                parsed.print_warnings();
+                Ok(Some(parsed.suite))
             } else {
                 let stder_output = stderr_handle.join().unwrap();
                 error!(stder_output);
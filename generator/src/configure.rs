@@ -6,7 +6,11 @@ use fs_err as fs;
 pub fn read_config() -> anyhow::Result<GeneratorConfig> {
     let config_path = get_config_path()?;
     let config_contents = fs::read_to_string(&config_path)?;
-    let root: ConfigRoot = serde_yml::from_str(&config_contents)?;
+    let root: ConfigRoot = if config_path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&config_contents)?
+    } else {
+        serde_yml::from_str(&config_contents)?
+    };
     Ok(root.into_config())
 }
 
@@ -36,7 +40,7 @@ impl ConfigRoot {
             .evaluator
             .languages
             .into_iter()
-            .map(|lang| lang.extension)
+            .flat_map(|lang| std::iter::once(lang.extension).chain(lang.aliases))
             .collect();
 
         config
@@ -46,7 +50,11 @@ impl ConfigRoot {
 #[derive(Debug, Deserialize, Default)]
 pub struct GeneratorConfig {
     pub editor: Option<String>,
+    /// 開啟編輯器時使用的完整指令，設定後取代 `editor`，支援 `{file}` 與 `{exe_dir}` 佔位符。
+    pub editor_command: Option<String>,
     pub plugins: Option<Vec<Plugin>>,
+    #[serde(default)]
+    pub allow_overwrite: bool,
     #[serde(skip_deserializing, default)]
     pub supported_code_types: Vec<String>,
 }
@@ -65,4 +73,6 @@ pub struct PartialEvaluatorConfig {
 #[derive(Debug, Deserialize)]
 pub struct PartialLanguageProfile {
     pub extension: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
 }
\ No newline at end of file
@@ -0,0 +1,149 @@
+use inquire::{CustomType, Text, error::InquireResult, validator::Validation};
+
+/// 邊界測資的限制條件。
+pub struct Constraints {
+    pub n_min: usize,
+    pub n_max: usize,
+    pub value_min: i64,
+    pub value_max: i64,
+    /// 輸入模板，`{n}` 代入資料數量，`{values}` 代入以空白分隔的數值，`\n` 代表換行。
+    pub template: String,
+}
+
+pub struct EdgeCase {
+    pub name: String,
+    pub input: String,
+}
+
+struct EdgeCaseSpec {
+    name: &'static str,
+    n: usize,
+    values: Vec<i64>,
+}
+
+type Strategy = fn(&Constraints) -> Option<EdgeCaseSpec>;
+
+const STRATEGIES: &[Strategy] = &[
+    |c| Some(uniform("n 最小、數值最小", c.n_min, c.value_min)),
+    |c| Some(uniform("n 最小、數值最大", c.n_min, c.value_max)),
+    |c| (c.n_min < 1 && 1 <= c.n_max).then(|| uniform("n = 1", 1, c.value_min)),
+    |c| Some(uniform("n 最大、數值最小", c.n_max, c.value_min)),
+    |c| Some(uniform("n 最大、數值最大", c.n_max, c.value_max)),
+    |c| {
+        Some(EdgeCaseSpec {
+            name: "n 最大、最小最大值交錯",
+            n: c.n_max,
+            values: (0..c.n_max)
+                .map(|i| if i % 2 == 0 { c.value_min } else { c.value_max })
+                .collect(),
+        })
+    },
+];
+
+fn uniform(name: &'static str, n: usize, value: i64) -> EdgeCaseSpec {
+    EdgeCaseSpec {
+        name,
+        n,
+        values: vec![value; n],
+    }
+}
+
+fn render(template: &str, spec: &EdgeCaseSpec) -> String {
+    let values = spec
+        .values
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    template
+        .replace("\\n", "\n")
+        .replace("{n}", &spec.n.to_string())
+        .replace("{values}", &values)
+}
+
+/// 依限制條件套用所有策略，產生不重複的邊界測資輸入。
+pub fn generate_edge_cases(constraints: &Constraints) -> Vec<EdgeCase> {
+    let mut cases: Vec<EdgeCase> = Vec::new();
+    for spec in STRATEGIES
+        .iter()
+        .filter_map(|strategy| strategy(constraints))
+    {
+        let input = render(&constraints.template, &spec);
+        if cases.iter().all(|case| case.input != input) {
+            cases.push(EdgeCase {
+                name: spec.name.to_owned(),
+                input,
+            });
+        }
+    }
+    cases
+}
+
+pub fn prompt_constraints() -> InquireResult<Constraints> {
+    let n_min = CustomType::<usize>::new("n 最小值:").prompt()?;
+    let n_max = CustomType::<usize>::new("n 最大值:")
+        .with_validator(move |value: &usize| {
+            Ok(if *value >= n_min {
+                Validation::Valid
+            } else {
+                Validation::Invalid("需大於等於 n 最小值".into())
+            })
+        })
+        .prompt()?;
+    let value_min = CustomType::<i64>::new("數值最小值:").prompt()?;
+    let value_max = CustomType::<i64>::new("數值最大值:")
+        .with_validator(move |value: &i64| {
+            Ok(if *value >= value_min {
+                Validation::Valid
+            } else {
+                Validation::Invalid("需大於等於數值最小值".into())
+            })
+        })
+        .prompt()?;
+    let template = Text::new("輸入模板:")
+        .with_default("{n}\\n{values}")
+        .with_help_message("{n} 代入數量，{values} 代入數值，\\n 代表換行")
+        .prompt()?;
+
+    Ok(Constraints {
+        n_min,
+        n_max,
+        value_min,
+        value_max,
+        template,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraints(n_min: usize, n_max: usize) -> Constraints {
+        Constraints {
+            n_min,
+            n_max,
+            value_min: -5,
+            value_max: 9,
+            template: "{n}\\n{values}".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_generate_edge_cases() {
+        let cases = generate_edge_cases(&constraints(0, 3));
+        let inputs: Vec<&str> = cases.iter().map(|case| case.input.as_str()).collect();
+
+        assert!(inputs.contains(&"0\n"));
+        assert!(inputs.contains(&"1\n-5"));
+        assert!(inputs.contains(&"3\n9 9 9"));
+        assert!(inputs.contains(&"3\n-5 9 -5"));
+    }
+
+    #[test]
+    fn test_generate_edge_cases_dedup() {
+        let cases = generate_edge_cases(&constraints(2, 2));
+        let inputs: Vec<&str> = cases.iter().map(|case| case.input.as_str()).collect();
+
+        assert_eq!(inputs, vec!["2\n-5 -5", "2\n9 9", "2\n-5 9"]);
+    }
+}
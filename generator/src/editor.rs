@@ -0,0 +1,33 @@
+use fs_err as fs;
+use inquire::InquireError;
+use shared::{build_native_shell_command_in, get_exe_dir};
+use std::{env, io, process};
+
+/// 以設定檔中的 `editor_command` 編輯一段文字並回傳編輯後的內容。
+///
+/// 可用佔位符:
+/// - `{file}`: 暫存檔路徑，未出現時附加於指令最後
+/// - `{exe_dir}`: generator 執行檔所在的資料夾
+pub fn edit_with_command(command: &str) -> Result<String, InquireError> {
+    let exe_dir = get_exe_dir()?;
+    let file = env::temp_dir().join(format!("generator-{}.txt", process::id()));
+    fs::write(&file, "")?;
+
+    let quoted_file = format!("\"{}\"", file.display());
+    let mut command = command.replace("{exe_dir}", &format!("\"{}\"", exe_dir.display()));
+    if command.contains("{file}") {
+        command = command.replace("{file}", &quoted_file);
+    } else {
+        command = format!("{command} {quoted_file}");
+    }
+
+    let status = build_native_shell_command_in(&command, &exe_dir)?.status();
+    let content = fs::read_to_string(&file);
+    let _ = fs::remove_file(&file);
+
+    let status = status?;
+    if !status.success() {
+        return Err(io::Error::other(format!("編輯器結束代碼異常: {status}")).into());
+    }
+    Ok(content?)
+}
@@ -0,0 +1,122 @@
+use anyhow::bail;
+use fs_err as fs;
+use owo_colors::OwoColorize;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::path::{Path, PathBuf};
+
+use crate::structs::{TestCase, TestLimit, TestSuite};
+use crate::warn;
+
+/// 讀取 Codeforces (Polygon) 題目包。
+///
+/// `tests/` 中無副檔名的檔案為輸入，同名的 `.a` 檔為答案；缺少答案的輸入會被略過。
+/// 若題目包含有 `problem.xml`，則一併讀取其中的時間與記憶體限制。
+pub fn import_codeforces_package(package_dir: &Path) -> anyhow::Result<TestSuite> {
+    let tests_dir = package_dir.join("tests");
+    let mut inputs: Vec<PathBuf> = fs::read_dir(&tests_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_none())
+        .collect();
+    // 依測資編號排序，使 `2` 排在 `10` 之前
+    inputs.sort_by_key(|path| {
+        let number = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<u32>().ok());
+        (number, path.clone())
+    });
+
+    let mut cases = Vec::new();
+    for input_path in inputs {
+        let answer_path = input_path.with_extension("a");
+        if !answer_path.is_file() {
+            warn!("缺少答案檔，已略過", input_path.display());
+            continue;
+        }
+        cases.push(TestCase {
+            input: fs::read_to_string(&input_path)?,
+            answer: fs::read_to_string(&answer_path)?,
+            id: cases.len() as u32 + 1,
+            name: None,
+        });
+    }
+    if cases.is_empty() {
+        bail!("{} 中找不到任何測資", tests_dir.display());
+    }
+
+    let problem_xml = package_dir.join("problem.xml");
+    let limit = if problem_xml.is_file() {
+        parse_problem_limits(&fs::read_to_string(&problem_xml)?)?.into_option()
+    } else {
+        None
+    };
+
+    Ok(TestSuite { limit, cases })
+}
+
+/// 取出 `problem.xml` 中第一組 `time-limit` (ms) 與 `memory-limit` (bytes，轉換為 KiB)。
+fn parse_problem_limits(xml: &str) -> anyhow::Result<TestLimit> {
+    let mut reader = Reader::from_str(xml);
+    let mut limit = TestLimit::new();
+    let mut current_tag: Option<Vec<u8>> = None;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(tag) => current_tag = Some(tag.name().as_ref().to_vec()),
+            Event::End(_) => current_tag = None,
+            Event::Text(text) => {
+                let value = text.decode()?;
+                let value = value.trim();
+                match current_tag.as_deref() {
+                    Some(b"time-limit") if limit.time.is_none() => {
+                        limit.time = value.parse().ok();
+                    }
+                    Some(b"memory-limit") if limit.memory.is_none() => {
+                        limit.memory = value
+                            .parse::<u64>()
+                            .ok()
+                            .and_then(|bytes| (bytes / 1024).try_into().ok());
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_problem_limits() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<problem revision="3" short-name="a-plus-b">
+  <judging input-file="" output-file="">
+    <testset name="tests">
+      <time-limit>1000</time-limit>
+      <memory-limit>268435456</memory-limit>
+      <test-count>2</test-count>
+    </testset>
+    <testset name="pretests">
+      <time-limit>3000</time-limit>
+    </testset>
+  </judging>
+</problem>"#;
+        let limit = parse_problem_limits(xml).unwrap();
+        assert_eq!(limit.time, Some(1000));
+        assert_eq!(limit.memory, Some(262144));
+    }
+
+    #[test]
+    fn test_parse_problem_limits_missing() {
+        let limit = parse_problem_limits("<problem></problem>").unwrap();
+        assert!(limit.into_option().is_none());
+    }
+}
@@ -0,0 +1,2 @@
+mod codeforces;
+pub use codeforces::import_codeforces_package;
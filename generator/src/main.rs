@@ -1,17 +1,60 @@
+use clap::Parser;
 use code_file::generate_code_file;
-use configure::{apply_config, read_config};
+use configure::{GeneratorConfig, apply_config, read_config};
+use fs_err as fs;
+use importers::import_codeforces_package;
 use inquire::{InquireError, error::InquireResult};
 use owo_colors::OwoColorize;
+use script::read_script;
+use std::path::{Path, PathBuf};
+use std::process;
+use suite_diff::print_suite_diff;
 use test_cases::generate_test_case;
+use structs::TestSuite;
+use utils::{FileStatus, test_create_file};
 
 mod advanced;
 mod code_file;
 mod configure;
+mod edge_cases;
+mod editor;
+mod importers;
+mod preview;
+mod script;
+mod state;
 mod structs;
+mod suite_diff;
 mod test_cases;
 mod utils;
 
+/// Generator - Test Case Generator
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// 從 Codeforces (Polygon) 題目包匯入測資並直接產生配置檔，不進入互動模式。
+    /// Import tests from a Codeforces (Polygon) problem package and write a config file non-interactively.
+    #[arg(long("cf-import"), value_name = "PACKAGE_DIR")]
+    cf_import: Option<PathBuf>,
+
+    /// 匯入時輸出的配置檔路徑 (預設為題目包資料夾名稱加上 .yaml)。
+    /// Output config path for imports (defaults to the package directory name with .yaml).
+    #[arg(short, long, requires = "cf_import")]
+    output: Option<String>,
+
+    /// 比較兩份配置檔，列出新增、移除與修改的測資及限制變更，不進入互動模式。
+    /// Compare two config files and report added, removed and modified cases and limit changes.
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"], conflicts_with = "cf_import")]
+    diff: Option<Vec<String>>,
+
+    /// 依照腳本檔 (yaml) 描述的測資與限制產生配置檔，不進入互動模式，適用於 CI。
+    /// Generate a config file from a YAML script describing cases and limits, without any prompts.
+    #[arg(long, value_name = "SPEC", conflicts_with_all = ["cf_import", "diff"])]
+    script: Option<PathBuf>,
+}
+
 fn main() {
+    let args = Args::parse();
+
     let config_result = read_config();
     if let Err(e) = &config_result {
         warn!("錯誤，已忽略設置檔案", e);
@@ -19,12 +62,82 @@ fn main() {
     let config = config_result.unwrap_or_default();
     apply_config(&config);
 
+    if let Some([old_path, new_path]) = args.diff.as_deref() {
+        if let Err(e) = print_suite_diff(old_path, new_path) {
+            error!("比較配置檔失敗", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(spec_path) = &args.script {
+        match run_script(spec_path, &config) {
+            Ok(path) => println!("{}", format!("成功創建 '{}'", path).green()),
+            Err(e) => {
+                error!("執行腳本失敗", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(package_dir) = &args.cf_import {
+        match import_package(package_dir, args.output, &config) {
+            Ok(path) => println!("{}", format!("成功創建 '{}'", path).green()),
+            Err(e) => {
+                error!("匯入題目包失敗", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
     let judge_config_path_option = resolve_inquire_error(generate_test_case(&config));
     if let Some(judge_config_path) = judge_config_path_option {
         resolve_inquire_error(generate_code_file(judge_config_path, &config));
     }
 }
 
+fn import_package(
+    package_dir: &Path,
+    output: Option<String>,
+    config: &GeneratorConfig,
+) -> anyhow::Result<String> {
+    let suite = import_codeforces_package(package_dir)?;
+
+    let path = match output {
+        Some(path) => path,
+        None => {
+            let name = std::path::absolute(package_dir)?
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "tests".to_owned());
+            format!("{name}.yaml")
+        }
+    };
+    write_suite(&path, &suite, config)?;
+    Ok(path)
+}
+
+fn run_script(spec_path: &Path, config: &GeneratorConfig) -> anyhow::Result<String> {
+    let spec = read_script(spec_path)?;
+    let path = spec.output.clone();
+    write_suite(&path, &spec.into_suite(), config)?;
+    Ok(path)
+}
+
+/// 寫出配置檔；檔案已存在且設定不允許覆蓋時回傳錯誤。
+fn write_suite(path: &str, suite: &TestSuite, config: &GeneratorConfig) -> anyhow::Result<()> {
+    match test_create_file(path) {
+        FileStatus::Exists if !config.allow_overwrite => anyhow::bail!("{} 已存在", path),
+        FileStatus::NotFound | FileStatus::Exists => {}
+        status => anyhow::bail!("{}: {}", path, status.to_str()),
+    }
+
+    fs::write(path, suite.to_config_string(path)?)?;
+    Ok(())
+}
+
 fn resolve_inquire_error<T>(result: InquireResult<T>) -> Option<T> {
     match result {
         Ok(value) => Some(value),
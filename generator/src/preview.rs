@@ -0,0 +1,51 @@
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use owo_colors::OwoColorize;
+
+use crate::structs::{TestCase, TestSuite};
+
+const PAGER: &str = "bat";
+
+/// 以唯讀方式預覽單筆測資序列化後的 YAML。
+///
+/// 優先使用 `bat` 分頁顯示；若找不到 `bat` 則直接輸出至終端機。
+/// 使用者在讀完輸入前離開 `bat` 不視為錯誤。
+pub fn preview_case(case: &TestCase) -> io::Result<()> {
+    let yaml = serde_yml::to_string(&TestSuite {
+        limit: None,
+        cases: vec![case.clone()],
+    })
+    .map_err(io::Error::other)?;
+
+    let child = Command::new(PAGER)
+        .args([
+            "--paging=always",
+            "--language=yaml",
+            "--file-name=preview.yaml",
+        ])
+        .stdin(Stdio::piped())
+        .spawn();
+
+    match child {
+        Ok(mut child) => {
+            let written = match child.stdin.take() {
+                Some(mut stdin) => stdin.write_all(yaml.as_bytes()),
+                None => Ok(()),
+            };
+            child.wait()?;
+            match written {
+                Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+                _ => {}
+            }
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            println!("{}", "--- 預覽 (唯讀) ---".dimmed());
+            print!("{}", yaml);
+            println!("{}", "------------------".dimmed());
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anyhow::{anyhow, bail};
+use fs_err as fs;
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::structs::{TestCase, TestLimit, TestSuite};
+
+/// `--script` 讀取的腳本格式，描述要產生的配置檔內容。
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptSpec {
+    /// 輸出的配置檔路徑。
+    pub output: String,
+    /// 時間限制 (ms)。
+    pub time_limit: Option<u64>,
+    /// 記憶體限制 (KiB)。
+    pub memory_limit: Option<u32>,
+    pub cases: Vec<ScriptCase>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptCase {
+    pub input: String,
+    pub answer: String,
+}
+
+/// 讀取並驗證腳本檔。
+pub fn read_script(path: &Path) -> anyhow::Result<ScriptSpec> {
+    let content = fs::read_to_string(path)?;
+    let spec: ScriptSpec =
+        serde_yml::from_str(&content).map_err(|e| anyhow!("{} 格式錯誤: {e}", path.display()))?;
+    spec.validate()?;
+    Ok(spec)
+}
+
+impl ScriptSpec {
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.output.trim().is_empty() {
+            bail!("output 不可為空");
+        }
+        if self.cases.is_empty() {
+            bail!("cases 至少需要一筆測資");
+        }
+        if self.time_limit == Some(0) {
+            bail!("time_limit 必須大於 0");
+        }
+        if self.memory_limit == Some(0) {
+            bail!("memory_limit 必須大於 0");
+        }
+        Ok(())
+    }
+
+    pub fn into_suite(self) -> TestSuite {
+        let limit = TestLimit {
+            memory: self.memory_limit,
+            time: self.time_limit,
+        };
+        let cases = self
+            .cases
+            .into_iter()
+            .enumerate()
+            .map(|(index, case)| TestCase {
+                input: case.input,
+                answer: case.answer,
+                id: index as u32 + 1,
+                name: None,
+            })
+            .collect();
+        TestSuite {
+            limit: limit.into_option(),
+            cases,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_spec_rejects_unknown_fields() {
+        let spec = "output: a.yaml\ntime: 1000\ncases:\n- input: '1'\n  answer: '1'\n";
+        assert!(serde_yml::from_str::<ScriptSpec>(spec).is_err());
+    }
+
+    #[test]
+    fn test_script_spec_requires_cases() {
+        let spec: ScriptSpec = serde_yml::from_str("output: a.yaml\ncases: []\n").unwrap();
+        assert!(spec.validate().is_err());
+    }
+}
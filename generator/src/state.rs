@@ -0,0 +1,31 @@
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use shared::get_exe_dir;
+use std::path::PathBuf;
+
+const STATE_FILE_NAME: &str = "generator_state.yaml";
+
+/// 跨次執行保留的使用紀錄，用來預填下次的輸入。
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GeneratorState {
+    pub last_dir: Option<String>,
+    pub time: Option<u64>,
+    pub memory: Option<u32>,
+}
+
+fn state_path() -> anyhow::Result<PathBuf> {
+    Ok(get_exe_dir()?.join(STATE_FILE_NAME))
+}
+
+pub fn load_state() -> GeneratorState {
+    state_path()
+        .and_then(|path| Ok(fs::read_to_string(path)?))
+        .and_then(|contents| Ok(serde_yml::from_str(&contents)?))
+        .unwrap_or_default()
+}
+
+pub fn save_state(state: &GeneratorState) -> anyhow::Result<()> {
+    let yaml = serde_yml::to_string(state)?;
+    fs::write(state_path()?, yaml)?;
+    Ok(())
+}
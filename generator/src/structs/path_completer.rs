@@ -5,14 +5,23 @@ use inquire::{
     CustomUserError,
     autocompletion::{Autocomplete, Replacement},
 };
+use std::path::Path;
 
 use crate::utils::{FileStatus, test_create_file};
 
+/// 輸入含有 `/` 時，向下搜尋子資料夾的最大層數。
+const MAX_SCAN_DEPTH: usize = 3;
+const MAX_SUGGESTIONS: usize = 30;
+/// 標示建議路徑為既有檔案 (將被覆蓋) 的後綴。
+pub const OVERWRITE_SUFFIX: &str = " (overwrite)";
+
 #[derive(Clone, Default)]
 pub struct YamlPathCompleter {
     input: String,
     paths: Vec<String>,
     pub supported_code_types: Vec<String>,
+    pub allow_overwrite: bool,
+    pub existing_configs: bool,
 }
 
 impl YamlPathCompleter {
@@ -21,6 +30,17 @@ impl YamlPathCompleter {
         self
     }
 
+    pub fn allow_overwrite(mut self, allow: bool) -> Self {
+        self.allow_overwrite = allow;
+        self
+    }
+
+    /// 改為列出既有的 yaml/json 配置檔，用於匯入。
+    pub fn existing_configs(mut self, existing: bool) -> Self {
+        self.existing_configs = existing;
+        self
+    }
+
     fn update_input(&mut self, input: &str) -> Result<(), CustomUserError> {
         if input == self.input && !self.paths.is_empty() {
             return Ok(());
@@ -52,10 +72,45 @@ impl YamlPathCompleter {
             return Ok(());
         }
 
-        let entries = fs::read_dir(scan_dir)?.collect::<Result<Vec<_>, _>>()?;
+        let max_depth = if input.contains('/') {
+            MAX_SCAN_DEPTH
+        } else {
+            0
+        };
+        self.collect_paths(&scan_dir, max_depth)?;
+
+        Ok(())
+    }
+
+    /// 只回傳 `dir` 本身無法讀取的錯誤；無法讀取的子資料夾與項目會被略過，
+    /// 以免單一資料夾的權限問題讓所有建議消失。
+    fn collect_paths(&mut self, dir: &Path, remaining_depth: usize) -> Result<(), CustomUserError> {
+        let entries: Vec<_> = fs::read_dir(dir)?.filter_map(Result::ok).collect();
 
         for entry in entries {
+            if self.paths.len() >= MAX_SUGGESTIONS {
+                break;
+            }
+
             let mut path = entry.path();
+            if path.is_dir() {
+                let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if remaining_depth > 0 && !is_hidden {
+                    // 輸入提示進行中無法輸出警告，因此直接略過
+                    let _ = self.collect_paths(&path, remaining_depth - 1);
+                }
+                continue;
+            }
+
+            if self.existing_configs {
+                if path.extension().is_some_and(|ext| {
+                    matches!(ext.to_str(), Some("yaml" | "yml" | "json" | "toml"))
+                }) {
+                    self.paths.push(display_path(&path));
+                }
+                continue;
+            }
+
             if path.is_file()
                 && path
                     .extension()
@@ -64,14 +119,13 @@ impl YamlPathCompleter {
                     .unwrap_or(false)
             {
                 path.set_extension("yaml");
-                let status = test_create_file(&path);
-                if matches!(status, FileStatus::NotFound) {
-                    self.paths.push(
-                        path.to_string_lossy()
-                            .replace("\\", "/")
-                            .trim_start_matches("./")
-                            .to_owned(),
-                    );
+                let display = display_path(&path);
+                match test_create_file(&path) {
+                    FileStatus::NotFound => self.paths.push(display),
+                    FileStatus::Exists if self.allow_overwrite => {
+                        self.paths.push(format!("{display}{OVERWRITE_SUFFIX}"))
+                    }
+                    _ => {}
                 }
             }
         }
@@ -99,18 +153,24 @@ impl Autocomplete for YamlPathCompleter {
     ) -> Result<Replacement, CustomUserError> {
         self.update_input(input)?;
 
-        Ok(if let Some(suggestion) = highlighted_suggestion {
-            Replacement::Some(suggestion)
-        } else {
-            let matches = self.fuzzy_sort(input);
-            matches
-                .first()
-                .map(|(path, _)| Replacement::Some(path.clone()))
-                .unwrap_or(Replacement::None)
-        })
+        let suggestion = highlighted_suggestion.or_else(|| {
+            self.fuzzy_sort(input)
+                .into_iter()
+                .next()
+                .map(|(path, _)| path)
+        });
+
+        Ok(suggestion.map(|path| path.trim_end_matches(OVERWRITE_SUFFIX).to_owned()))
     }
 }
 
+fn display_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace("\\", "/")
+        .trim_start_matches("./")
+        .to_owned()
+}
+
 fn fuzzy_sort(input: &str, vecs: &[String]) -> Vec<(String, i64)> {
     let mut matches: Vec<(String, i64)> = vecs
         .iter()
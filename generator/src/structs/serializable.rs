@@ -1,6 +1,9 @@
+use fs_err as fs;
 use owo_colors::OwoColorize;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use shared::get_exe_dir;
 use std::collections::VecDeque;
+use std::fmt;
 
 #[derive(Serialize)]
 pub struct TestSuite {
@@ -8,16 +11,59 @@ pub struct TestSuite {
     pub cases: Vec<TestCase>,
 }
 
-#[derive(Serialize)]
+impl TestSuite {
+    /// 依 `path` 的副檔名序列化，`.toml` 輸出 TOML，其餘輸出 yaml。
+    pub fn to_config_string(&self, path: &str) -> anyhow::Result<String> {
+        if path.ends_with(".toml") {
+            Ok(toml::to_string(self)?)
+        } else {
+            Ok(serde_yml::to_string(self)?)
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
 pub struct TestCase {
     pub input: String,
     pub answer: String,
     
     #[serde(skip_serializing)]
     pub id: u32,
+    #[serde(skip_serializing)]
+    pub name: Option<String>,
 }
 
-#[derive(Serialize)]
+impl TestCase {
+    /// 選單中顯示的標籤，附上輸入與答案的位元組數及行數，例如
+    /// `測資 3 — in: 42B (3 lines), ans: 7B (1 line)`。
+    pub fn summary_label(&self) -> String {
+        fn size(text: &str) -> String {
+            let lines = text.lines().count();
+            format!(
+                "{}B ({} {})",
+                text.len(),
+                lines,
+                if lines == 1 { "line" } else { "lines" }
+            )
+        }
+
+        format!(
+            "{}{} — in: {}, ans: {}",
+            if self.id == 0 {
+                "外來測資".to_owned()
+            } else {
+                format!("測資 {}", self.id)
+            },
+            self.name
+                .as_ref()
+                .map_or_else(String::new, |name| format!(" [{name}]")),
+            size(&self.input),
+            size(&self.answer)
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct TestLimit {
     pub memory: Option<u32>,
     pub time: Option<u64>,
@@ -39,7 +85,77 @@ impl TestLimit {
     }
 }
 
-pub fn parse_easy_test_suite(input: &str) -> TestSuite {
+/// `parse_easy_test_suite` 解析時略過的內容。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// `include` 的檔案無法讀取。
+    IncludeFailed {
+        file: String,
+        reason: String,
+    },
+    /// 標頭不是 `key 行數` 的形式。
+    BadHeader(String),
+    /// 標頭中的行數無法解析。
+    BadLineCount(String),
+    /// 區段的行數不足。
+    MissingLines {
+        key: String,
+        expected: usize,
+        found: usize,
+    },
+    /// limit 區段的參數未成對出現。
+    OddLimitTokens,
+    BadTime(String),
+    BadMemory(String),
+    UnknownLimit(String),
+    UnknownKey(String),
+    /// input 與 answer 的數量不同，多出的部分被捨棄。
+    Unpaired {
+        inputs: usize,
+        answers: usize,
+    },
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::IncludeFailed { file, reason } => write!(f, "無法讀取 `{file}`: {reason}"),
+            Self::BadHeader(header) => write!(f, "錯誤格式（應為 `key 行數`）: `{header}`"),
+            Self::BadLineCount(header) => write!(f, "行數無法解析於: `{header}`"),
+            Self::MissingLines {
+                key,
+                expected,
+                found,
+            } => write!(f, "期待 {expected} 行，但只取得 {found} 行，在 key `{key}`"),
+            Self::OddLimitTokens => write!(f, "limit 欄位格式錯誤：參數需成對出現"),
+            Self::BadTime(value) => write!(f, "時間格式錯誤: `{value}`"),
+            Self::BadMemory(value) => write!(f, "記憶體格式錯誤: `{value}`"),
+            Self::UnknownLimit(chunk) => write!(f, "limit 欄位未知格式: {chunk}"),
+            Self::UnknownKey(key) => write!(f, "忽略未知 key `{key}`"),
+            Self::Unpaired { inputs, answers } => write!(
+                f,
+                "input ({inputs} 筆) 與 answer ({answers} 筆) 數量不符，多出的部分已捨棄"
+            ),
+        }
+    }
+}
+
+/// `parse_easy_test_suite` 的結果，包含解析出的配置與略過內容的警告。
+pub struct ParsedSuite {
+    pub suite: TestSuite,
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl ParsedSuite {
+    /// 將警告以紅字印至 stderr。
+    pub fn print_warnings(&self) {
+        for warning in &self.warnings {
+            eprintln!("{}", format!("[Parse] {warning}").red());
+        }
+    }
+}
+
+pub fn parse_easy_test_suite(input: &str) -> ParsedSuite {
     let mut lines = input.lines();
     let mut limit = TestLimit {
         memory: None,
@@ -48,15 +164,25 @@ pub fn parse_easy_test_suite(input: &str) -> TestSuite {
     let mut inputs = VecDeque::new();
     let mut answers = VecDeque::new();
     let mut cases = Vec::new();
+    let mut warnings = Vec::new();
 
     while let Some(header) = lines.next() {
+        if let Some((key, file)) = parse_include_header(header) {
+            match read_included_file(file) {
+                Ok(content) if key == "input" => inputs.push_back(content),
+                Ok(content) => answers.push_back(content),
+                Err(e) => warnings.push(ParseWarning::IncludeFailed {
+                    file: file.to_owned(),
+                    reason: e.to_string(),
+                }),
+            }
+            continue;
+        }
+
         let parts: Vec<&str> = header.split_whitespace().collect();
 
         if parts.len() != 2 {
-            eprintln!(
-                "{}",
-                format!("[Parse] 錯誤格式（應為 `key 行數`）: `{}`", header).red()
-            );
+            warnings.push(ParseWarning::BadHeader(header.to_owned()));
             continue;
         }
 
@@ -64,7 +190,7 @@ pub fn parse_easy_test_suite(input: &str) -> TestSuite {
         let count = match parts[1].parse::<usize>() {
             Ok(c) => c,
             Err(_) => {
-                eprintln!("{}", format!("[Parse] 行數無法解析於: `{}`", header).red());
+                warnings.push(ParseWarning::BadLineCount(header.to_owned()));
                 continue;
             }
         };
@@ -74,14 +200,11 @@ pub fn parse_easy_test_suite(input: &str) -> TestSuite {
             match lines.next() {
                 Some(line) => content.push(line.to_string()),
                 None => {
-                    eprintln!(
-                        "{}",
-                        format!(
-                            "[Parse] 期待 {} 行，但只取得 {} 行，在 key `{}`",
-                            count, i, key
-                        )
-                        .red()
-                    );
+                    warnings.push(ParseWarning::MissingLines {
+                        key: key.to_owned(),
+                        expected: count,
+                        found: i,
+                    });
                     break;
                 }
             }
@@ -98,7 +221,7 @@ pub fn parse_easy_test_suite(input: &str) -> TestSuite {
                 let tokens: Vec<&str> = joined.split_whitespace().collect();
 
                 if tokens.len() % 2 != 0 {
-                    eprintln!("{}", "[Parse] limit 欄位格式錯誤：參數需成對出現".red());
+                    warnings.push(ParseWarning::OddLimitTokens);
                     continue;
                 }
 
@@ -108,38 +231,34 @@ pub fn parse_easy_test_suite(input: &str) -> TestSuite {
                     match chunk {
                         ["time", val] => match val.parse::<u64>() {
                             Ok(ms) => limit.time = Some(ms),
-                            Err(_) => {
-                                eprintln!("{}", format!("[Parse] 時間格式錯誤: `{}`", val).red())
-                            }
+                            Err(_) => warnings.push(ParseWarning::BadTime(val.to_string())),
                         },
                         ["memory", val] => match val.parse::<u32>() {
                             Ok(mem) => limit.memory = Some(mem),
-                            Err(_) => {
-                                eprintln!("{}", format!("[Parse] 記憶體格式錯誤: `{}`", val).red())
-                            }
+                            Err(_) => warnings.push(ParseWarning::BadMemory(val.to_string())),
                         },
-                        _ => {
-                            eprintln!(
-                                "{}",
-                                format!("[Parse] limit 欄位未知格式: {:?}", chunk).red()
-                            );
-                        }
+                        _ => warnings.push(ParseWarning::UnknownLimit(format!("{:?}", chunk))),
                     }
                 }
             }
             "input" => inputs.push_back(joined),
             "answer" => answers.push_back(joined),
-            other => {
-                eprintln!("{}", format!("[Parse] 忽略未知 key `{}`", other).red());
-            }
+            other => warnings.push(ParseWarning::UnknownKey(other.to_owned())),
         }
     }
 
+    if inputs.len() != answers.len() {
+        warnings.push(ParseWarning::Unpaired {
+            inputs: inputs.len(),
+            answers: answers.len(),
+        });
+    }
     while let (Some(input), Some(answer)) = (inputs.pop_front(), answers.pop_front()) {
         cases.push(TestCase {
             input,
             answer,
             id: 0,
+            name: None,
         });
     }
 
@@ -149,7 +268,27 @@ pub fn parse_easy_test_suite(input: &str) -> TestSuite {
         None
     };
 
-    TestSuite { limit, cases }
+    ParsedSuite {
+        suite: TestSuite { limit, cases },
+        warnings,
+    }
+}
+
+/// 解析 `input include <檔案>` 或 `answer include <檔案>` 形式的標頭。
+fn parse_include_header(header: &str) -> Option<(&str, &str)> {
+    let (key, rest) = header.trim().split_once(char::is_whitespace)?;
+    let file = rest.trim_start().strip_prefix("include")?;
+    if !file.starts_with(char::is_whitespace) || !matches!(key, "input" | "answer") {
+        return None;
+    }
+    Some((key, file.trim()))
+}
+
+/// 讀取外部檔案內容，相對路徑以執行檔所在資料夾為基準。
+fn read_included_file(file: &str) -> anyhow::Result<String> {
+    let path = get_exe_dir()?.join(file);
+    let content = fs::read_to_string(path)?;
+    Ok(content.trim_end_matches(['\r', '\n']).to_owned())
 }
 
 #[cfg(test)]
@@ -157,7 +296,7 @@ mod tests {
     use super::*;
 
     fn case(input: &str) -> TestSuite {
-        parse_easy_test_suite(input)
+        parse_easy_test_suite(input).suite
     }
 
     #[test]
@@ -246,4 +385,74 @@ yo
         // banana 是未知 key，會被略過
         assert_eq!(config.cases.len(), 1);
     }
+
+    #[test]
+    fn test_parse_collects_warnings() {
+        let input = r#"
+limit 2
+time abc
+memory 256
+banana 1
+oops
+input 1
+1 2
+input 1
+3 4
+answer 1
+3
+"#;
+        let parsed = parse_easy_test_suite(input.trim());
+        assert_eq!(parsed.suite.cases.len(), 1);
+        assert_eq!(
+            parsed.warnings,
+            vec![
+                ParseWarning::BadTime("abc".to_owned()),
+                ParseWarning::UnknownKey("banana".to_owned()),
+                ParseWarning::Unpaired {
+                    inputs: 2,
+                    answers: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summary_label() {
+        let case = TestCase {
+            input: "3\n1 2 3".to_owned(),
+            answer: "6".to_owned(),
+            id: 3,
+            name: None,
+        };
+        assert_eq!(
+            case.summary_label(),
+            "測資 3 — in: 7B (2 lines), ans: 1B (1 line)"
+        );
+    }
+
+    #[test]
+    fn test_parse_include_file() {
+        let path = std::env::temp_dir().join("offline-judge-include-test.in");
+        std::fs::write(&path, "4 5\n").unwrap();
+
+        let input = format!("input include {}\nanswer 1\n9", path.display());
+        let config = case(&input);
+
+        assert_eq!(config.cases.len(), 1);
+        assert_eq!(config.cases[0].input, "4 5");
+        assert_eq!(config.cases[0].answer, "9");
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_parse_include_missing_file() {
+        let input = r#"
+input include this/file/does/not/exist.in
+answer 1
+3
+"#;
+        let config = case(input.trim());
+        assert!(config.cases.is_empty());
+    }
 }
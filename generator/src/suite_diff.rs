@@ -0,0 +1,193 @@
+use owo_colors::OwoColorize;
+use similar::{ChangeTag, TextDiff};
+use std::collections::{HashMap, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::structs::{TestLimit, TestSuite};
+use crate::test_cases::read_test_suite;
+use crate::utils::with_ellipsis;
+
+/// 兩份配置檔之間的差異，測資以輸入內容的雜湊值配對。
+pub struct SuiteDiff {
+    pub limit_changes: Vec<String>,
+    /// 僅存在於新配置檔的測資索引
+    pub added: Vec<usize>,
+    /// 僅存在於舊配置檔的測資索引
+    pub removed: Vec<usize>,
+    /// 輸入相同但答案不同的測資索引 (舊, 新)
+    pub modified: Vec<(usize, usize)>,
+    pub unchanged: usize,
+}
+
+fn input_hash(input: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    input.trim_end().hash(&mut hasher);
+    hasher.finish()
+}
+
+pub fn diff_suites(old: &TestSuite, new: &TestSuite) -> SuiteDiff {
+    // 重複的輸入依出現順序一對一配對
+    let mut old_by_hash: HashMap<u64, VecDeque<usize>> = HashMap::new();
+    for (index, case) in old.cases.iter().enumerate() {
+        old_by_hash
+            .entry(input_hash(&case.input))
+            .or_default()
+            .push_back(index);
+    }
+
+    let mut diff = SuiteDiff {
+        limit_changes: limit_changes(old.limit.as_ref(), new.limit.as_ref()),
+        added: Vec::new(),
+        removed: Vec::new(),
+        modified: Vec::new(),
+        unchanged: 0,
+    };
+    for (new_index, case) in new.cases.iter().enumerate() {
+        let matched = old_by_hash
+            .get_mut(&input_hash(&case.input))
+            .and_then(VecDeque::pop_front);
+        match matched {
+            Some(old_index) if old.cases[old_index].answer.trim_end() == case.answer.trim_end() => {
+                diff.unchanged += 1;
+            }
+            Some(old_index) => diff.modified.push((old_index, new_index)),
+            None => diff.added.push(new_index),
+        }
+    }
+    diff.removed = old_by_hash.into_values().flatten().collect();
+    diff.removed.sort_unstable();
+    diff
+}
+
+fn limit_changes(old: Option<&TestLimit>, new: Option<&TestLimit>) -> Vec<String> {
+    fn show<T: std::fmt::Display>(value: Option<T>, unit: &str) -> String {
+        value.map_or_else(|| "未設定".to_owned(), |v| format!("{v} {unit}"))
+    }
+
+    let mut changes = Vec::new();
+    let old_time = old.and_then(|limit| limit.time);
+    let new_time = new.and_then(|limit| limit.time);
+    if old_time != new_time {
+        changes.push(format!(
+            "時間限制: {} → {}",
+            show(old_time, "ms"),
+            show(new_time, "ms")
+        ));
+    }
+    let old_memory = old.and_then(|limit| limit.memory);
+    let new_memory = new.and_then(|limit| limit.memory);
+    if old_memory != new_memory {
+        changes.push(format!(
+            "記憶體限制: {} → {}",
+            show(old_memory, "KiB"),
+            show(new_memory, "KiB")
+        ));
+    }
+    changes
+}
+
+/// 讀取兩份配置檔並印出兩者之間的差異。
+pub fn print_suite_diff(old_path: &str, new_path: &str) -> anyhow::Result<()> {
+    let old = read_test_suite(old_path, 1)?;
+    let new = read_test_suite(new_path, 1)?;
+    let diff = diff_suites(&old, &new);
+
+    println!("{}", format!("比較 {old_path} → {new_path}").bold());
+    for change in &diff.limit_changes {
+        println!("{}", format!("~ {change}").yellow());
+    }
+    for &index in &diff.added {
+        let case = &new.cases[index];
+        println!(
+            "{}",
+            format!("+ 新增 測資 #{} ({})", index + 1, preview(&case.input)).green()
+        );
+    }
+    for &index in &diff.removed {
+        let case = &old.cases[index];
+        println!(
+            "{}",
+            format!("- 移除 測資 #{} ({})", index + 1, preview(&case.input)).red()
+        );
+    }
+    for &(old_index, new_index) in &diff.modified {
+        println!(
+            "{}",
+            format!("~ 修改 測資 #{} → #{} 的答案", old_index + 1, new_index + 1).yellow()
+        );
+        print_answer_diff(&old.cases[old_index].answer, &new.cases[new_index].answer);
+    }
+
+    println!(
+        "總計: 新增 {}、移除 {}、修改 {}、未變更 {}",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.modified.len(),
+        diff.unchanged
+    );
+    Ok(())
+}
+
+fn preview(input: &str) -> String {
+    with_ellipsis(input.lines().next().unwrap_or_default(), 30)
+}
+
+fn print_answer_diff(old: &str, new: &str) {
+    let diff = TextDiff::from_lines(old.trim_end(), new.trim_end());
+    for change in diff.iter_all_changes() {
+        let line = change
+            .as_str()
+            .unwrap_or_default()
+            .trim_end_matches(['\r', '\n']);
+        match change.tag() {
+            ChangeTag::Delete => println!("    {}", format!("- {line}").red()),
+            ChangeTag::Insert => println!("    {}", format!("+ {line}").green()),
+            ChangeTag::Equal => println!("      {line}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::TestCase;
+
+    fn suite(cases: &[(&str, &str)], time: Option<u64>) -> TestSuite {
+        TestSuite {
+            limit: TestLimit { memory: None, time }.into_option(),
+            cases: cases
+                .iter()
+                .map(|&(input, answer)| TestCase {
+                    input: input.to_owned(),
+                    answer: answer.to_owned(),
+                    id: 0,
+                    name: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_suites_matches_cases_by_input() {
+        let old = suite(&[("1 2", "3"), ("2 2", "4"), ("5 5", "10")], Some(1000));
+        let new = suite(&[("2 2", "5"), ("1 2", "3\n"), ("7 7", "14")], Some(2000));
+        let diff = diff_suites(&old, &new);
+
+        assert_eq!(diff.added, vec![2]);
+        assert_eq!(diff.removed, vec![2]);
+        assert_eq!(diff.modified, vec![(1, 0)]);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.limit_changes, vec!["時間限制: 1000 ms → 2000 ms"]);
+    }
+
+    #[test]
+    fn test_diff_suites_pairs_duplicate_inputs() {
+        let old = suite(&[("1", "1"), ("1", "1")], None);
+        let new = suite(&[("1", "1")], None);
+        let diff = diff_suites(&old, &new);
+
+        assert_eq!(diff.removed, vec![1]);
+        assert_eq!(diff.unchanged, 1);
+        assert!(diff.limit_changes.is_empty());
+    }
+}
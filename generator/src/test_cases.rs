@@ -1,36 +1,63 @@
-use fs_err::File;
-use std::{fmt, io::Write};
+use fs_err::{self as fs, File};
+use serde::Deserialize;
+use std::{fmt, io::Write, path::Path};
 
 use inquire::{
-    CustomType, Editor, InquireError, Select, Text, error::InquireResult, validator::Validation,
+    Confirm, CustomType, Editor, InquireError, Select, Text, error::InquireResult,
+    validator::Validation,
 };
 use owo_colors::OwoColorize;
 
 use crate::{
     advanced::{prompt_advanced_options, update_by_advanced},
     configure::GeneratorConfig,
+    edge_cases::{generate_edge_cases, prompt_constraints},
+    editor::edit_with_command,
     escapable,
+    preview::preview_case,
+    state::{GeneratorState, load_state, save_state},
     structs::{
-        CaseInputCompleter, LabelWithOptionIndex, OPEN_EDITOR_MAGIC, OptionalInput, TestCase,
-        TestLimit, TestSuite, YamlPathCompleter,
+        CaseInputCompleter, LabelWithOptionIndex, OPEN_EDITOR_MAGIC, OVERWRITE_SUFFIX,
+        OptionalInput, TestCase, TestLimit, TestSuite, YamlPathCompleter,
     },
-    utils::{ESCAPABLE, file_path_validator},
+    utils::{ESCAPABLE, FileStatus, file_path_validator, test_create_file, with_ellipsis},
+    warn,
 };
 
 pub fn generate_test_case(config: &GeneratorConfig) -> InquireResult<String> {
     let mut test_cases: Vec<TestCase> = Vec::new();
     let mut test_limit = TestLimit::new();
     let mut id: u32 = 1;
+    let state = load_state();
+    // 本次回答過限制提示後，即使清空也照實記錄，讓使用者能忘掉先前的限制
+    let mut time_answered = false;
+    let mut memory_answered = false;
 
-    let file = Text::new("配置檔案名稱:")
-        .with_validator(with_yaml_path_validator)
+    let allow_overwrite = config.allow_overwrite;
+    let mut file_dialogue = Text::new("配置檔案名稱:")
+        .with_validator(move |input: &str| with_yaml_path_validator(input, allow_overwrite))
         .with_formatter(&|i| with_yaml(i))
-        .with_help_message("副檔名為yaml，若沒有會自動補上")
+        .with_help_message("副檔名為yaml或toml，若沒有會自動補上yaml")
         .with_autocomplete(
-            YamlPathCompleter::default().supported_code_types(config.supported_code_types.clone()),
-        )
-        .prompt()?;
-    let file_path = with_yaml(&file);
+            YamlPathCompleter::default()
+                .supported_code_types(config.supported_code_types.clone())
+                .allow_overwrite(allow_overwrite),
+        );
+    if let Some(last_dir) = &state.last_dir {
+        file_dialogue = file_dialogue.with_initial_value(last_dir);
+    }
+    let file_path = loop {
+        let file_path = with_yaml(&file_dialogue.clone().prompt()?);
+        if allow_overwrite
+            && matches!(test_create_file(&file_path), FileStatus::Exists)
+            && !Confirm::new("配置檔已存在，確定要覆蓋嗎?")
+                .with_default(false)
+                .prompt()?
+        {
+            continue;
+        }
+        break file_path;
+    };
 
     loop {
         let action = Select::new(
@@ -42,73 +69,119 @@ pub fn generate_test_case(config: &GeneratorConfig) -> InquireResult<String> {
         match action {
             Action::Add => {
                 let input = escapable!(
-                    input_text_or_editor(&format!("測資 {} 輸入:", id)),
+                    input_text_or_editor(config, &format!("測資 {} 輸入:", id)),
                     continue
                 )?;
                 let answer = escapable!(
-                    input_text_or_editor(&format!("測資 {} 答案:", id)),
+                    input_text_or_editor(config, &format!("測資 {} 答案:", id)),
                     continue
                 )?;
 
-                let test_case = TestCase { input, answer, id };
+                let test_case = TestCase {
+                    input,
+                    answer,
+                    id,
+                    name: None,
+                };
                 test_cases.push(test_case);
                 id += 1;
             }
             Action::Delete => {
-                let mut options: Vec<LabelWithOptionIndex> = test_cases
-                    .iter()
-                    .enumerate()
-                    .map(|(index, case)| {
-                        LabelWithOptionIndex::new(
-                            Some(index),
-                            format!(
-                                "{} ({}字)",
-                                if case.id == 0 {
-                                    "外來測資".to_owned()
-                                } else {
-                                    format!("測資 {}", case.id)
-                                },
-                                case.input.len() + case.answer.len()
-                            ),
-                        )
-                    })
-                    .collect();
-                options.push(LabelWithOptionIndex::new(None, "取消".to_owned()));
                 let selection = escapable!(
-                    Select::new(
+                    select_case(
                         &format!("選擇要刪除的測資 (共 {} 筆):", test_cases.len()),
-                        options
-                    )
-                    .prompt(),
+                        &test_cases
+                    ),
                     continue
                 )?;
-                if let Some(index) = selection.index {
+                if let Some(index) = selection {
                     test_cases.remove(index);
                 };
             }
+            Action::GenerateEdgeCases => {
+                let constraints = escapable!(prompt_constraints(), continue)?;
+                for edge_case in generate_edge_cases(&constraints) {
+                    println!("{}", format!("[{}]", edge_case.name).bright_blue());
+                    println!("{}", with_ellipsis(&edge_case.input, 200));
+                    let answer = escapable!(
+                        input_text_or_editor(config, &format!("測資 {} 答案 (Esc 略過):", id)),
+                        continue
+                    )?;
+                    test_cases.push(TestCase {
+                        input: edge_case.input,
+                        answer,
+                        id,
+                        name: Some(edge_case.name),
+                    });
+                    id += 1;
+                }
+            }
+            Action::ImportConfig => {
+                let path = escapable!(
+                    Text::new("匯入配置檔路徑:")
+                        .with_help_message("yaml 或 json 格式")
+                        .with_autocomplete(YamlPathCompleter::default().existing_configs(true))
+                        .prompt(),
+                    continue
+                )?;
+                match read_test_suite(&path, id) {
+                    Ok(suite) => {
+                        id += suite.cases.len() as u32;
+                        println!(
+                            "{}",
+                            format!("已匯入 {} 筆測資", suite.cases.len()).bright_blue()
+                        );
+                        test_cases.extend(suite.cases);
+                        if let Some(limit) = suite.limit {
+                            test_limit.time = test_limit.time.or(limit.time);
+                            test_limit.memory = test_limit.memory.or(limit.memory);
+                        }
+                    }
+                    Err(e) => {
+                        warn!("無法匯入配置檔", e);
+                    }
+                }
+            }
+            Action::Preview => {
+                let selection = escapable!(
+                    select_case(
+                        &format!("選擇要預覽的測資 (共 {} 筆):", test_cases.len()),
+                        &test_cases
+                    ),
+                    continue
+                )?;
+                // 預覽失敗不應中斷編輯，以免遺失尚未儲存的測資
+                if let Some(index) = selection
+                    && let Err(e) = preview_case(&test_cases[index])
+                {
+                    warn!("無法預覽測資", e);
+                }
+            }
             Action::LimitTime => {
                 let init_text: String;
                 let mut dialogue = CustomType::<OptionalInput<u64>>::new("最長時間 (ms):")
                     .with_help_message("留白表示使用預設值");
-                if let Some(max_time) = test_limit.time {
+                if let Some(max_time) = test_limit.time.or(state.time) {
                     init_text = max_time.to_string();
                     dialogue = dialogue.with_starting_input(&init_text);
                 }
 
                 let max_time: Option<u64> = escapable!(dialogue.prompt(), continue)?.value;
                 test_limit.time = max_time;
+                time_answered = true;
             }
             Action::LimitMemory => {
                 let init_text: String;
                 let mut dialogue = CustomType::<OptionalInput<u32>>::new("最大記憶體 (KiB):")
                     .with_help_message("留白表示使用預設值");
-                if let Some(max_memory) = test_limit.memory {
+                if let Some(max_memory) = test_limit.memory.or(state.memory) {
                     init_text = max_memory.to_string();
                     dialogue = dialogue.with_starting_input(&init_text);
                 }
 
                 let max_memory = escapable!(dialogue.prompt(), continue)?.value;
                 test_limit.memory = max_memory;
+                memory_answered = true;
             }
             Action::Submit => break,
             Action::ListMore => {
@@ -118,17 +191,40 @@ pub fn generate_test_case(config: &GeneratorConfig) -> InquireResult<String> {
         }
     }
 
+    let new_state = GeneratorState {
+        last_dir: Path::new(&file_path)
+            .parent()
+            .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| format!("{dir}/")),
+        time: if time_answered {
+            test_limit.time
+        } else {
+            test_limit.time.or(state.time)
+        },
+        memory: if memory_answered {
+            test_limit.memory
+        } else {
+            test_limit.memory.or(state.memory)
+        },
+    };
+
     let mut file = File::create(&file_path)?;
-    let yaml = serde_yml::to_string(&TestSuite {
+    let content = TestSuite {
         limit: test_limit.into_option(),
         cases: test_cases,
-    })
+    }
+    .to_config_string(&file_path)
     .unwrap();
 
-    file.write_all(yaml.as_bytes())?;
+    file.write_all(content.as_bytes())?;
 
     println!("{}", format!("成功創建 '{}'", &file_path).green());
 
+    if let Err(e) = save_state(&new_state) {
+        warn!("無法儲存使用紀錄", e);
+    }
+
     Ok(file_path)
 }
 
@@ -136,6 +232,9 @@ pub fn generate_test_case(config: &GeneratorConfig) -> InquireResult<String> {
 enum Action {
     Add,
     Delete,
+    GenerateEdgeCases,
+    ImportConfig,
+    Preview,
     Submit,
     LimitTime,
     LimitMemory,
@@ -146,6 +245,9 @@ impl Action {
     const LIST: &'static [Action] = &[
         Self::Add,
         Self::Delete,
+        Self::GenerateEdgeCases,
+        Self::ImportConfig,
+        Self::Preview,
         Self::LimitTime,
         Self::LimitMemory,
         Self::ListMore,
@@ -158,6 +260,9 @@ impl fmt::Display for Action {
         match self {
             Self::Add => write!(f, "新增測資"),
             Self::Delete => write!(f, "刪除測資"),
+            Self::GenerateEdgeCases => write!(f, "生成邊界測資"),
+            Self::ImportConfig => write!(f, "匯入配置檔"),
+            Self::Preview => write!(f, "預覽測資"),
             Self::LimitTime => write!(f, "限制時間"),
             Self::LimitMemory => write!(f, "限制記憶體"),
             Self::ListMore => write!(f, "進階操作"),
@@ -166,23 +271,85 @@ impl fmt::Display for Action {
     }
 }
 
+#[derive(Deserialize)]
+struct ImportedSuite {
+    limit: Option<TestLimit>,
+    cases: Vec<ImportedCase>,
+}
+
+#[derive(Deserialize)]
+struct ImportedCase {
+    input: String,
+    answer: Option<String>,
+    #[serde(default)]
+    answers: Vec<String>,
+}
+
+/// 讀取既有的 yaml/json/toml 配置檔，測資編號自 `first_id` 起重新編排。
+/// 含多個答案的測資只保留第一個答案。
+pub fn read_test_suite(path: &str, first_id: u32) -> anyhow::Result<TestSuite> {
+    let content = fs::read_to_string(path)?;
+    let suite: ImportedSuite = if path.ends_with(".toml") {
+        toml::from_str(&content)?
+    } else {
+        // json 為 yaml 的子集，可共用同一個解析器
+        serde_yml::from_str(&content)?
+    };
+
+    let cases = suite
+        .cases
+        .into_iter()
+        .enumerate()
+        .map(|(index, case)| {
+            let answer = case
+                .answer
+                .or_else(|| case.answers.into_iter().next())
+                .ok_or_else(|| anyhow::anyhow!("第 {} 筆測資缺少 answer 欄位", index + 1))?;
+            Ok(TestCase {
+                input: case.input,
+                answer,
+                id: first_id + index as u32,
+                name: None,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(TestSuite {
+        limit: suite.limit,
+        cases,
+    })
+}
+
+fn select_case(message: &str, test_cases: &[TestCase]) -> InquireResult<Option<usize>> {
+    let mut options: Vec<LabelWithOptionIndex> = test_cases
+        .iter()
+        .enumerate()
+        .map(|(index, case)| LabelWithOptionIndex::new(Some(index), case.summary_label()))
+        .collect();
+    options.push(LabelWithOptionIndex::new(None, "取消".to_owned()));
+    Ok(Select::new(message, options).prompt()?.index)
+}
+
 fn with_yaml_path_validator(
     input: &str,
+    allow_overwrite: bool,
 ) -> Result<Validation, Box<dyn std::error::Error + Send + Sync>> {
-    file_path_validator(with_yaml(input))
+    file_path_validator(with_yaml(input), allow_overwrite)
 }
 
 fn with_yaml(input: &str) -> String {
+    // 按下 Enter 時 inquire 會直接送出選取的建議，須移除覆蓋標記
+    let input = input.strip_suffix(OVERWRITE_SUFFIX).unwrap_or(input);
     if input.trim().is_empty() {
         "".to_owned()
-    } else if input.ends_with(".yaml") || input.ends_with(".yml") {
+    } else if input.ends_with(".yaml") || input.ends_with(".yml") || input.ends_with(".toml") {
         input.to_owned()
     } else {
         format!("{}.yaml", input)
     }
 }
 
-fn input_text_or_editor(message: &str) -> Result<String, InquireError> {
+fn input_text_or_editor(config: &GeneratorConfig, message: &str) -> Result<String, InquireError> {
     let input = Text::new(message)
         .with_autocomplete(CaseInputCompleter)
         .with_help_message(ESCAPABLE)
@@ -195,8 +362,45 @@ fn input_text_or_editor(message: &str) -> Result<String, InquireError> {
         })
         .prompt()?;
     if input == OPEN_EDITOR_MAGIC {
-        Editor::new(message).prompt()
+        match &config.editor_command {
+            Some(command) if !command.trim().is_empty() => edit_with_command(command),
+            _ => Editor::new(message).prompt(),
+        }
     } else {
         Ok(input)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inquire::autocompletion::Autocomplete;
+
+    #[test]
+    fn test_submitted_overwrite_suggestion_targets_existing_file() {
+        let dir = std::env::temp_dir().join("offline-judge-overwrite-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.cpp"), "").unwrap();
+        std::fs::write(dir.join("a.yaml"), "").unwrap();
+
+        let mut completer = YamlPathCompleter::default()
+            .supported_code_types(vec!["cpp".to_owned()])
+            .allow_overwrite(true);
+        let input = format!("{}/", dir.to_string_lossy().replace('\\', "/"));
+        let suggestions = completer.get_suggestions(&input).unwrap();
+        let existing = format!("{input}a.yaml");
+        // Enter 送出的是建議本身，而非 get_completion 的結果
+        let submitted = suggestions
+            .iter()
+            .find(|suggestion| suggestion.ends_with(OVERWRITE_SUFFIX))
+            .expect("expected an overwrite suggestion");
+
+        assert_eq!(with_yaml(submitted), existing);
+        assert!(matches!(
+            with_yaml_path_validator(submitted, true).unwrap(),
+            Validation::Valid
+        ));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -71,6 +71,7 @@ impl FileStatus {
 
 pub fn file_path_validator(
     input: String,
+    allow_overwrite: bool,
 ) -> Result<Validation, Box<dyn std::error::Error + Send + Sync>> {
     if input.is_empty() {
         return Ok(Validation::Invalid(ErrorMessage::Custom(
@@ -79,6 +80,7 @@ pub fn file_path_validator(
     }
     match test_create_file(&input) {
         FileStatus::NotFound => Ok(Validation::Valid),
+        FileStatus::Exists if allow_overwrite => Ok(Validation::Valid),
         status @ FileStatus::Exists => Ok(Validation::Invalid(ErrorMessage::Custom(format!(
             "{} ({})",
             &status.to_str(),
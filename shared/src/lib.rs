@@ -2,7 +2,8 @@
 use ::shlex;
 use std::io;
 use std::process::Command;
-use std::{env, path::PathBuf};
+use std::env;
+use std::path::{Path, PathBuf};
 
 pub fn get_exe_dir() -> io::Result<PathBuf> {
     if cfg!(debug_assertions) {
@@ -17,19 +18,57 @@ pub fn get_exe_dir() -> io::Result<PathBuf> {
     }
 }
 
+const CONFIG_FILE_NAME: &str = "config.yaml";
+/// 向上搜尋時於每個資料夾依序尋找的設定檔檔名。
+const ANCESTOR_CONFIG_FILE_NAMES: [&str; 2] = [CONFIG_FILE_NAME, "config.toml"];
+/// 向上搜尋設定檔時，遇到含有此檔案的資料夾即停止。
+const ROOT_SENTINEL: &str = ".ojroot";
+
 pub fn get_config_path() -> io::Result<PathBuf> {
-    Ok(get_exe_dir()?.join("config.yaml"))
+    let path = get_exe_dir()?.join(CONFIG_FILE_NAME);
+    if path.exists() {
+        return Ok(path);
+    }
+    Ok(find_config_in_ancestors(&env::current_dir()?).unwrap_or(path))
+}
+
+/// 從 `start` 開始逐層往上尋找 `config.yaml` 或 `config.toml`，
+/// 直到檔案系統根目錄或含有 `.ojroot` 的資料夾為止。
+pub fn find_config_in_ancestors(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        let found = ANCESTOR_CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file());
+        if found.is_some() {
+            return found;
+        }
+        if dir.join(ROOT_SENTINEL).exists() {
+            break;
+        }
+    }
+    None
 }
 
-#[cfg(windows)]
 pub fn build_native_shell_command(command_string: &str) -> io::Result<Command> {
+    build_native_shell_command_in(command_string, &env::current_dir()?)
+}
+
+pub fn build_native_shell_command_in(command_string: &str, cwd: &Path) -> io::Result<Command> {
+    let mut cmd = native_shell_command(command_string)?;
+    cmd.current_dir(cwd);
+    Ok(cmd)
+}
+
+#[cfg(windows)]
+fn native_shell_command(command_string: &str) -> io::Result<Command> {
     let mut cmd = Command::new("powershell");
     cmd.arg("-Command").arg(command_string);
     Ok(cmd)
 }
 
 #[cfg(unix)]
-pub fn build_native_shell_command(command_string: &str) -> io::Result<Command> {
+fn native_shell_command(command_string: &str) -> io::Result<Command> {
     let args = shlex::split(command_string).ok_or_else(|| {
         io::Error::new(
             io::ErrorKind::InvalidInput,
@@ -50,9 +89,41 @@ pub fn build_native_shell_command(command_string: &str) -> io::Result<Command> {
 }
 
 #[cfg(not(any(unix, windows)))]
-pub fn build_native_shell_command(_command_string: &str) -> io::Result<Command> {
+fn native_shell_command(_command_string: &str) -> io::Result<Command> {
     Err(io::Error::new(
         io::ErrorKind::Unsupported,
         "This platform is not supported for native shell commands.",
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_find_config_in_ancestors_stops_at_root_sentinel() {
+        let root = env::temp_dir().join(format!("offline-judge-ancestors-{}", std::process::id()));
+        let project = root.join("project");
+        let nested = project.join("src").join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join("config.toml"), "").unwrap();
+
+        assert_eq!(
+            find_config_in_ancestors(&nested),
+            Some(root.join("config.toml"))
+        );
+
+        fs::write(project.join(ROOT_SENTINEL), "").unwrap();
+        assert_eq!(find_config_in_ancestors(&nested), None);
+
+        fs::write(project.join("config.toml"), "").unwrap();
+        fs::write(project.join("config.yaml"), "").unwrap();
+        assert_eq!(
+            find_config_in_ancestors(&nested),
+            Some(project.join("config.yaml"))
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}